@@ -0,0 +1,193 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+
+use crate::{
+    game_theme::ThemeColors,
+    keybindings::{Action, Key, KeyBindings, KEYS_PER_ACTION},
+};
+
+const ACTION_COLUMN_WIDTH: usize = 16;
+const KEY_COLUMN_WIDTH: usize = 9;
+
+pub enum BindingsAction {
+    None,
+    Close, // keeping the edited `bindings`
+}
+
+/// Settings sub-screen for rebinding the in-game keys. Every action has two
+/// slots; a key that's already taken by another action is refused.
+#[derive(Debug)]
+pub struct BindingsEditor {
+    pub bindings: KeyBindings,
+    selected: usize, // index into Action::ALL
+    slot: usize,     // 0..KEYS_PER_ACTION
+    capturing: bool,
+    message: Option<String>,
+}
+
+impl BindingsEditor {
+    pub fn new(bindings: &KeyBindings) -> Self {
+        Self {
+            bindings: bindings.clone(),
+            selected: 0,
+            slot: 0,
+            capturing: false,
+            message: None,
+        }
+    }
+
+    fn action(&self) -> Action {
+        Action::ALL[self.selected]
+    }
+
+    fn bind(&mut self, code: KeyCode) {
+        let action = self.action();
+        let key = match code {
+            KeyCode::Char(c) => Key(KeyCode::Char(c.to_ascii_lowercase())),
+            code => Key(code),
+        };
+        if !Key::is_bindable(key.0) {
+            self.message = Some("That key can't be bound".to_string());
+            return;
+        }
+        match self.bindings.action_for(key.0) {
+            Some(other) if other == action => {
+                self.message = Some(format!(
+                    "{} is already bound to {}",
+                    key.label(),
+                    other.name()
+                ));
+            }
+            Some(other) => {
+                self.message = Some(format!(
+                    "{} is already bound to {}, unbind it there first",
+                    key.label(),
+                    other.name()
+                ));
+            }
+            None => {
+                let keys = self.bindings.keys_mut(action);
+                if self.slot < keys.len() {
+                    keys[self.slot] = key;
+                } else {
+                    keys.push(key);
+                }
+                self.message = None;
+            }
+        }
+    }
+
+    fn unbind(&mut self) {
+        let action = self.action();
+        let keys = self.bindings.keys_mut(action);
+        if self.slot >= keys.len() {
+            return;
+        }
+        if keys.len() == 1 {
+            self.message = Some(format!("{} needs at least one key", action.name()));
+            return;
+        }
+        keys.remove(self.slot);
+        self.message = None;
+    }
+
+    pub fn handle_key(&mut self, key_event: KeyEvent) -> BindingsAction {
+        if self.capturing {
+            self.capturing = false;
+            // Esc backs out, so it can only be kept, not newly bound
+            if key_event.code != KeyCode::Esc {
+                self.bind(key_event.code);
+            }
+            return BindingsAction::None;
+        }
+
+        match key_event.code {
+            KeyCode::Esc => return BindingsAction::Close,
+            KeyCode::Up => {
+                self.selected = (self.selected + Action::ALL.len() - 1) % Action::ALL.len();
+            }
+            KeyCode::Down => self.selected = (self.selected + 1) % Action::ALL.len(),
+            KeyCode::Left | KeyCode::Right | KeyCode::Tab => {
+                self.slot = (self.slot + 1) % KEYS_PER_ACTION;
+            }
+            KeyCode::Enter => {
+                // the second slot only opens up once the first is filled
+                self.slot = self.slot.min(self.bindings.keys(self.action()).len());
+                self.capturing = true;
+                self.message = None;
+            }
+            KeyCode::Backspace | KeyCode::Delete => self.unbind(),
+            KeyCode::Char('r') => {
+                self.bindings = KeyBindings::default();
+                self.message = Some("Restored the default keys".to_string());
+            }
+            _ => {}
+        }
+        BindingsAction::None
+    }
+
+    pub fn draw(&self, frame: &mut Frame, area: Rect, colors: &ThemeColors, selection: Style) {
+        let block = Block::default()
+            .title(Line::from("Key Bindings").style(Style::default().fg(colors.title)))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick)
+            .style(Style::default().fg(colors.accent));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let text = Style::default().fg(colors.text);
+        let mut lines = Vec::new();
+        for (i, action) in Action::ALL.iter().enumerate() {
+            let keys = self.bindings.keys(*action);
+            let mut spans = vec![Span::styled(
+                format!(
+                    "{} {:<width$}",
+                    if i == self.selected { ">" } else { " " },
+                    action.name(),
+                    width = ACTION_COLUMN_WIDTH
+                ),
+                text,
+            )];
+            for slot in 0..KEYS_PER_ACTION {
+                let is_selected = i == self.selected && slot == self.slot;
+                let label = if is_selected && self.capturing {
+                    "press…".to_string()
+                } else {
+                    keys.get(slot).map(Key::label).unwrap_or_default()
+                };
+                let style = if is_selected { selection } else { text };
+                spans.push(Span::raw(" "));
+                spans.push(Span::styled(
+                    format!("[{:^width$}]", label, width = KEY_COLUMN_WIDTH),
+                    style,
+                ));
+            }
+            lines.push(Line::from(spans));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(if self.capturing {
+            "Press the new key, or Esc to cancel"
+        } else {
+            "↑↓←→ select  Enter rebind  Del unbind  R reset  Esc done"
+        }));
+        if let Some(message) = self.message.as_ref() {
+            lines.push(Line::from(message.as_str()).style(Style::default().fg(colors.ball).bold()));
+        }
+
+        let [content] = Layout::vertical([Constraint::Length(lines.len() as u16)])
+            .flex(Flex::Center)
+            .areas(inner);
+        frame.render_widget(
+            Paragraph::new(lines)
+                .style(text)
+                .alignment(Alignment::Center),
+            content,
+        );
+    }
+}
@@ -0,0 +1,237 @@
+use std::path::PathBuf;
+
+use crate::{color::ColorSupport, game::GameType, helpers::validate_name};
+
+pub const USAGE: &str = "\
+Usage: terminal-pong [OPTIONS]
+
+Options:
+  --mode <MODE>          Skip the main menu and start a match: vs-ai, friend, screensaver
+  --p1 <NAME>            Player 1 name or profile (max 16 chars, needs --mode)
+  --p2 <NAME>            Player 2 name or profile (max 16 chars, needs --mode)
+  --difficulty <LEVEL>   easy, normal, hard or a number from 0.0 to 2.0 (needs --mode)
+  --theme <THEME>        Color theme, e.g. monokai, gruvbox-dark, high-contrast
+  --seed <N>             Seed the game's random number generator
+  --first-to <N>         End matches when a player reaches N points
+  --colors <DEPTH>       Override color detection: truecolor, 256, 16, mono
+  --config <PATH>        Read and write settings at PATH
+  --profiles <PATH>      Read and write player profiles at PATH
+  -h, --help             Print this help
+  -V, --version          Print the version
+";
+
+#[derive(Debug, Default)]
+pub struct CliOptions {
+    pub config_path: Option<PathBuf>,
+    pub profiles_path: Option<PathBuf>,
+    pub mode: Option<GameType>,
+    pub player_names: [Option<String>; 2],
+    pub difficulty: Option<f32>,
+    // checked against the built-in and custom themes once those are loaded
+    pub theme: Option<String>,
+    pub seed: Option<u64>,
+    pub first_to: Option<u32>,
+    pub color_support: Option<ColorSupport>,
+}
+
+#[derive(Debug)]
+pub enum CliCommand {
+    Run(CliOptions),
+    Help,
+    Version,
+}
+
+/// Parses the arguments after the program name. Errors are meant to be shown
+/// to the user as-is.
+pub fn parse_args<I>(args: I) -> Result<CliCommand, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut options = CliOptions::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        // accept both `--flag value` and `--flag=value`
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value)),
+            _ => (arg.clone(), None),
+        };
+        let mut value = |name: &str| -> Result<String, String> {
+            match inline_value {
+                Some(value) => Ok(value.to_string()),
+                None => args
+                    .next()
+                    .ok_or_else(|| format!("{} requires a value", name)),
+            }
+        };
+
+        match flag.as_str() {
+            "-h" | "--help" => return Ok(CliCommand::Help),
+            "-V" | "--version" => return Ok(CliCommand::Version),
+            "--config" => options.config_path = Some(PathBuf::from(value("--config")?)),
+            "--profiles" => options.profiles_path = Some(PathBuf::from(value("--profiles")?)),
+            "--mode" => options.mode = Some(parse_mode(&value("--mode")?)?),
+            "--p1" => options.player_names[0] = Some(parse_name("--p1", &value("--p1")?)?),
+            "--p2" => options.player_names[1] = Some(parse_name("--p2", &value("--p2")?)?),
+            "--difficulty" => options.difficulty = Some(parse_difficulty(&value("--difficulty")?)?),
+            "--theme" => options.theme = Some(value("--theme")?),
+            "--seed" => {
+                let seed = value("--seed")?;
+                options.seed = Some(seed.parse().map_err(|_| {
+                    format!("invalid --seed \"{}\": expected a whole number", seed)
+                })?);
+            }
+            "--first-to" => {
+                let points = value("--first-to")?;
+                options.first_to = match points.parse::<u32>() {
+                    Ok(n) if n > 0 => Some(n),
+                    _ => {
+                        return Err(format!(
+                            "invalid --first-to \"{}\": expected a positive number",
+                            points
+                        ))
+                    }
+                };
+            }
+            "--colors" => {
+                let depth = value("--colors")?;
+                options.color_support = Some(ColorSupport::from_name(&depth).ok_or_else(|| {
+                    format!(
+                        "invalid --colors \"{}\": expected truecolor, 256, 16 or mono",
+                        depth
+                    )
+                })?);
+            }
+            _ => return Err(format!("unexpected argument \"{}\"", arg)),
+        }
+    }
+
+    // these only describe the match started with --mode
+    if options.mode.is_none() {
+        let match_only = [
+            ("--p1", options.player_names[0].is_some()),
+            ("--p2", options.player_names[1].is_some()),
+            ("--difficulty", options.difficulty.is_some()),
+        ];
+        if let Some((flag, _)) = match_only.iter().find(|(_, given)| *given) {
+            return Err(format!("{} requires --mode", flag));
+        }
+    }
+
+    Ok(CliCommand::Run(options))
+}
+
+fn parse_mode(mode: &str) -> Result<GameType, String> {
+    match mode {
+        "vs-ai" => Ok(GameType::AgainstAi),
+        "friend" => Ok(GameType::WithFriend),
+        "screensaver" => Ok(GameType::ScreenSaver),
+        _ => Err(format!(
+            "invalid --mode \"{}\": expected vs-ai, friend or screensaver",
+            mode
+        )),
+    }
+}
+
+fn parse_name(flag: &str, name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(format!("{} must not be empty", flag));
+    }
+    validate_name(name).map_err(|err| format!("{} \"{}\" {}", flag, name, err))?;
+    Ok(name.to_string())
+}
+
+fn parse_difficulty(level: &str) -> Result<f32, String> {
+    match level.to_ascii_lowercase().as_str() {
+        "easy" => Ok(0.4),
+        "normal" => Ok(1.0),
+        "hard" => Ok(1.6),
+        _ => match level.parse::<f32>() {
+            Ok(d) if (0.0..=2.0).contains(&d) => Ok(d),
+            _ => Err(format!(
+                "invalid --difficulty \"{}\": expected easy, normal, hard or 0.0-2.0",
+                level
+            )),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<CliOptions, String> {
+        match parse_args(args.iter().map(|arg| arg.to_string()))? {
+            CliCommand::Run(options) => Ok(options),
+            other => panic!("expected options, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_a_full_match() {
+        let options = parse(&[
+            "--mode",
+            "friend",
+            "--p1",
+            "Alice",
+            "--p2=Bob",
+            "--difficulty",
+            "hard",
+            "--first-to",
+            "7",
+            "--seed=42",
+        ])
+        .unwrap();
+        assert_eq!(options.mode, Some(GameType::WithFriend));
+        assert_eq!(
+            options.player_names,
+            [Some("Alice".to_string()), Some("Bob".to_string())]
+        );
+        assert_eq!(options.difficulty, Some(1.6));
+        assert_eq!(options.first_to, Some(7));
+        assert_eq!(options.seed, Some(42));
+    }
+
+    #[test]
+    fn help_and_version_win() {
+        let parse_command = |args: &[&str]| parse_args(args.iter().map(|arg| arg.to_string()));
+        assert!(matches!(parse_command(&["-h"]), Ok(CliCommand::Help)));
+        assert!(matches!(
+            parse_command(&["--mode", "vs-ai", "--version"]),
+            Ok(CliCommand::Version)
+        ));
+    }
+
+    #[test]
+    fn difficulty_takes_names_and_numbers() {
+        assert_eq!(parse_difficulty("Easy"), Ok(0.4));
+        assert_eq!(parse_difficulty("1.25"), Ok(1.25));
+        assert!(parse_difficulty("2.5").is_err());
+        assert!(parse_difficulty("brutal").is_err());
+    }
+
+    #[test]
+    fn rejects_bad_values() {
+        assert!(parse(&["--mode", "solo"]).is_err());
+        assert!(parse(&["--first-to", "0"]).is_err());
+        assert!(parse(&["--seed", "-1"]).is_err());
+        assert!(parse(&["--colors", "8"]).is_err());
+        assert!(parse(&["--mode", "vs-ai", "--p1", "  "]).is_err());
+        assert_eq!(
+            parse(&["--mode"]).unwrap_err(),
+            "--mode requires a value".to_string()
+        );
+        assert!(parse(&["--bogus"]).is_err());
+    }
+
+    #[test]
+    fn match_options_need_a_mode() {
+        assert_eq!(
+            parse(&["--p2", "Bob"]).unwrap_err(),
+            "--p2 requires --mode".to_string()
+        );
+        assert!(parse(&["--difficulty", "easy"]).is_err());
+        assert_eq!(parse(&["--first-to", "3"]).unwrap().first_to, Some(3));
+    }
+}
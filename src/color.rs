@@ -0,0 +1,394 @@
+use ratatui::style::Color;
+
+/// Approximate RGB value of any ratatui color, using the xterm defaults for
+/// the 16 named colors and the 256-color palette. `Reset` has no fixed value.
+pub fn to_rgb(color: Color) -> Option<(u8, u8, u8)> {
+    match color {
+        Color::Reset => None,
+        Color::Rgb(r, g, b) => Some((r, g, b)),
+        Color::Indexed(i) => Some(indexed_to_rgb(i)),
+        Color::Black => Some(ANSI_16[0]),
+        Color::Red => Some(ANSI_16[1]),
+        Color::Green => Some(ANSI_16[2]),
+        Color::Yellow => Some(ANSI_16[3]),
+        Color::Blue => Some(ANSI_16[4]),
+        Color::Magenta => Some(ANSI_16[5]),
+        Color::Cyan => Some(ANSI_16[6]),
+        Color::Gray => Some(ANSI_16[7]),
+        Color::DarkGray => Some(ANSI_16[8]),
+        Color::LightRed => Some(ANSI_16[9]),
+        Color::LightGreen => Some(ANSI_16[10]),
+        Color::LightYellow => Some(ANSI_16[11]),
+        Color::LightBlue => Some(ANSI_16[12]),
+        Color::LightMagenta => Some(ANSI_16[13]),
+        Color::LightCyan => Some(ANSI_16[14]),
+        Color::White => Some(ANSI_16[15]),
+    }
+}
+
+// xterm's default values for the 16 ANSI colors
+const ANSI_16: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+fn indexed_to_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI_16[index as usize],
+        16..=231 => {
+            // 6x6x6 color cube
+            let i = index - 16;
+            let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+            (level(i / 36), level((i / 6) % 6), level(i % 6))
+        }
+        232..=255 => {
+            let gray = 8 + (index - 232) * 10;
+            (gray, gray, gray)
+        }
+    }
+}
+
+/// Mixes `amount` (0.0-1.0) of `to` into `from`. `Reset` stands in as black,
+/// since the terminal's own background color can't be known.
+pub fn blend(from: Color, to: Color, amount: f32) -> Color {
+    let (r1, g1, b1) = to_rgb(from).unwrap_or((0, 0, 0));
+    let (r2, g2, b2) = to_rgb(to).unwrap_or((0, 0, 0));
+    let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * amount).round() as u8;
+    Color::Rgb(mix(r1, r2), mix(g1, g2), mix(b1, b2))
+}
+
+/// Hue in degrees (0-360), saturation and lightness in 0.0-1.0.
+pub fn rgb_to_hsl((r, g, b): (u8, u8, u8)) -> (f32, f32, f32) {
+    let (r, g, b) = (r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+    let delta = max - min;
+    if delta == 0.0 {
+        return (0.0, 0.0, l);
+    }
+
+    let s = delta / (1.0 - (2.0 * l - 1.0).abs());
+    let h = if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    (h, s, l)
+}
+
+pub fn hsl_to_rgb((h, s, l): (f32, f32, f32)) -> (u8, u8, u8) {
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let h = h.rem_euclid(360.0) / 60.0;
+    let x = c * (1.0 - (h.rem_euclid(2.0) - 1.0).abs());
+    let (r, g, b) = match h as u8 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = l - c / 2.0;
+    let channel = |v: f32| ((v + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+    (channel(r), channel(g), channel(b))
+}
+
+/// How many colors the terminal can show. Themes are written in 24-bit RGB
+/// and mapped down to the nearest palette entry on lesser terminals.
+/// `Monochrome` drops color entirely and relies on glyphs and attributes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSupport {
+    TrueColor,
+    Ansi256,
+    Ansi16,
+    Monochrome,
+}
+
+impl ColorSupport {
+    /// Guesses from `COLORTERM` and `TERM`, the same variables most terminal
+    /// programs look at. A non-empty `NO_COLOR` (https://no-color.org) wins.
+    pub fn detect() -> Self {
+        if std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+            return ColorSupport::Monochrome;
+        }
+
+        let colorterm = std::env::var("COLORTERM")
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let term = std::env::var("TERM")
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        if colorterm == "truecolor" || colorterm == "24bit" || term.contains("direct") {
+            ColorSupport::TrueColor
+        } else if term.contains("256") {
+            ColorSupport::Ansi256
+        } else if term.is_empty() && cfg!(windows) {
+            // Windows Terminal and recent conhost both do 24-bit color
+            ColorSupport::TrueColor
+        } else {
+            ColorSupport::Ansi16
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "truecolor" | "24bit" => Some(ColorSupport::TrueColor),
+            "256" => Some(ColorSupport::Ansi256),
+            "16" => Some(ColorSupport::Ansi16),
+            "mono" | "none" => Some(ColorSupport::Monochrome),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ColorSupport::TrueColor => "Truecolor",
+            ColorSupport::Ansi256 => "256 colors",
+            ColorSupport::Ansi16 => "16 colors",
+            ColorSupport::Monochrome => "Monochrome",
+        }
+    }
+
+    pub fn is_monochrome(&self) -> bool {
+        *self == ColorSupport::Monochrome
+    }
+
+    /// Maps `color` to the closest color this terminal can display.
+    pub fn map(&self, color: Color) -> Color {
+        match (self, color) {
+            (ColorSupport::Monochrome, _) => Color::Reset,
+            (ColorSupport::TrueColor, _) | (_, Color::Reset) => color,
+            (ColorSupport::Ansi256, Color::Rgb(r, g, b)) => {
+                // the first 16 entries are user-configurable, skip them
+                let index = (16..=255)
+                    .min_by_key(|&i| distance((r, g, b), indexed_to_rgb(i)))
+                    .unwrap_or(16);
+                Color::Indexed(index)
+            }
+            (ColorSupport::Ansi256, _) => color,
+            (ColorSupport::Ansi16, Color::Indexed(_) | Color::Rgb(..)) => {
+                let rgb = to_rgb(color).unwrap_or((0, 0, 0));
+                let index = (0..16)
+                    .min_by_key(|&i| distance(rgb, ANSI_16[i]))
+                    .unwrap_or(0);
+                ANSI_16_COLORS[index]
+            }
+            (ColorSupport::Ansi16, _) => color,
+        }
+    }
+}
+
+const ANSI_16_COLORS: [Color; 16] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::Gray,
+    Color::DarkGray,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightYellow,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::LightCyan,
+    Color::White,
+];
+
+// "redmean" weighted distance, a cheap approximation of perceived difference
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let mean_r = (a.0 as i32 + b.0 as i32) / 2;
+    let dr = a.0 as i32 - b.0 as i32;
+    let dg = a.1 as i32 - b.1 as i32;
+    let db = a.2 as i32 - b.2 as i32;
+    (((512 + mean_r) * dr * dr) >> 8) as u32
+        + (4 * dg * dg) as u32
+        + (((767 - mean_r) * db * db) >> 8) as u32
+}
+
+/// Color vision deficiency to simulate when previewing a theme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cvd {
+    None,
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+}
+
+impl Cvd {
+    pub fn next(&self) -> Self {
+        match self {
+            Cvd::None => Cvd::Protanopia,
+            Cvd::Protanopia => Cvd::Deuteranopia,
+            Cvd::Deuteranopia => Cvd::Tritanopia,
+            Cvd::Tritanopia => Cvd::None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Cvd::None => "Normal vision",
+            Cvd::Protanopia => "Protanopia",
+            Cvd::Deuteranopia => "Deuteranopia",
+            Cvd::Tritanopia => "Tritanopia",
+        }
+    }
+
+    /// How `color` looks to someone with this deficiency, using the full
+    /// severity matrices from Machado, Oliveira & Fernandes (2009).
+    pub fn simulate(&self, color: Color) -> Color {
+        let matrix = match self {
+            Cvd::None => return color,
+            Cvd::Protanopia => [
+                [0.152286, 1.052583, -0.204868],
+                [0.114503, 0.786281, 0.099216],
+                [-0.003882, -0.048116, 1.051998],
+            ],
+            Cvd::Deuteranopia => [
+                [0.367322, 0.860646, -0.227968],
+                [0.280085, 0.672501, 0.047413],
+                [-0.011820, 0.042940, 0.968881],
+            ],
+            Cvd::Tritanopia => [
+                [1.255528, -0.076749, -0.178779],
+                [-0.078411, 0.930809, 0.147602],
+                [0.004733, 0.691367, 0.303900],
+            ],
+        };
+        let Some((r, g, b)) = to_rgb(color) else {
+            return color;
+        };
+
+        // the matrices work on linear light, not gamma-encoded sRGB
+        let to_linear = |v: u8| {
+            let v = v as f32 / 255.0;
+            if v <= 0.04045 {
+                v / 12.92
+            } else {
+                ((v + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        let to_srgb = |v: f32| {
+            let v = v.clamp(0.0, 1.0);
+            let v = if v <= 0.0031308 {
+                v * 12.92
+            } else {
+                1.055 * v.powf(1.0 / 2.4) - 0.055
+            };
+            (v * 255.0).round() as u8
+        };
+
+        let rgb = [to_linear(r), to_linear(g), to_linear(b)];
+        let channel = |row: [f32; 3]| to_srgb(row[0] * rgb[0] + row[1] * rgb[1] + row[2] * rgb[2]);
+        Color::Rgb(channel(matrix[0]), channel(matrix[1]), channel(matrix[2]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truecolor_and_monochrome() {
+        let color = Color::Rgb(12, 34, 56);
+        assert_eq!(ColorSupport::TrueColor.map(color), color);
+        assert_eq!(ColorSupport::Monochrome.map(color), Color::Reset);
+        assert_eq!(ColorSupport::Monochrome.map(Color::Red), Color::Reset);
+    }
+
+    #[test]
+    fn maps_down_to_256_colors() {
+        // cube corners and grays land on exact palette entries
+        assert_eq!(
+            ColorSupport::Ansi256.map(Color::Rgb(255, 0, 0)),
+            Color::Indexed(196)
+        );
+        assert_eq!(
+            ColorSupport::Ansi256.map(Color::Rgb(0, 0, 0)),
+            Color::Indexed(16)
+        );
+        assert_eq!(
+            ColorSupport::Ansi256.map(Color::Rgb(128, 128, 128)),
+            Color::Indexed(244)
+        );
+        assert_eq!(ColorSupport::Ansi256.map(Color::Blue), Color::Blue);
+        assert_eq!(ColorSupport::Ansi256.map(Color::Reset), Color::Reset);
+    }
+
+    #[test]
+    fn maps_down_to_16_colors() {
+        assert_eq!(ColorSupport::Ansi16.map(Color::Rgb(0, 0, 0)), Color::Black);
+        assert_eq!(
+            ColorSupport::Ansi16.map(Color::Rgb(250, 250, 250)),
+            Color::White
+        );
+        assert_eq!(
+            ColorSupport::Ansi16.map(Color::Indexed(196)),
+            Color::LightRed
+        );
+        assert_eq!(ColorSupport::Ansi16.map(Color::Cyan), Color::Cyan);
+    }
+
+    #[test]
+    fn parses_depth_names() {
+        assert_eq!(
+            ColorSupport::from_name("TrueColor"),
+            Some(ColorSupport::TrueColor)
+        );
+        assert_eq!(
+            ColorSupport::from_name("none"),
+            Some(ColorSupport::Monochrome)
+        );
+        assert_eq!(ColorSupport::from_name("8"), None);
+    }
+
+    #[test]
+    fn cvd_keeps_grays_and_normal_vision() {
+        let orange = Color::Rgb(244, 140, 6);
+        assert_eq!(Cvd::None.simulate(orange), orange);
+        assert_eq!(Cvd::Protanopia.simulate(Color::Reset), Color::Reset);
+        for cvd in [Cvd::Protanopia, Cvd::Deuteranopia, Cvd::Tritanopia] {
+            assert_eq!(cvd.simulate(Color::Rgb(0, 0, 0)), Color::Rgb(0, 0, 0));
+            assert_eq!(
+                cvd.simulate(Color::Rgb(255, 255, 255)),
+                Color::Rgb(255, 255, 255)
+            );
+        }
+    }
+
+    #[test]
+    fn cvd_brings_confused_colors_together() {
+        let seen = |cvd: Cvd, color: Color| to_rgb(cvd.simulate(color)).unwrap();
+        let (red, green) = (Color::Rgb(220, 40, 40), Color::Rgb(40, 180, 40));
+        let (blue, green_yellow) = (Color::Rgb(40, 80, 220), Color::Rgb(60, 200, 120));
+        let before = distance(to_rgb(red).unwrap(), to_rgb(green).unwrap());
+        for cvd in [Cvd::Protanopia, Cvd::Deuteranopia] {
+            assert!(distance(seen(cvd, red), seen(cvd, green)) < before);
+        }
+        let before = distance(to_rgb(blue).unwrap(), to_rgb(green_yellow).unwrap());
+        let after = distance(
+            seen(Cvd::Tritanopia, blue),
+            seen(Cvd::Tritanopia, green_yellow),
+        );
+        assert!(after < before);
+    }
+}
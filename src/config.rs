@@ -0,0 +1,155 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{game_theme::GameTheme, hires::RenderMode, keybindings::KeyBindings};
+
+pub const APP_DIR_NAME: &str = "terminal-pong";
+const CONFIG_FILE_NAME: &str = "config.toml";
+const THEMES_DIR_NAME: &str = "themes";
+
+/// User settings persisted between launches.
+///
+/// Missing keys fall back to their defaults and unknown keys are ignored, so
+/// older and newer config files both keep loading.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub default_difficulty_vs_ai: f32,
+    pub default_difficulty_with_friend: f32,
+    pub default_difficulty_screensaver: f32,
+    pub theme: String,
+    pub render_mode: RenderMode,
+    pub reduced_motion: bool,
+    pub max_fps: u32,
+    pub mouse_control: bool,
+    pub key_bindings: KeyBindings,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            default_difficulty_vs_ai: 0.8,
+            default_difficulty_with_friend: 1.0,
+            default_difficulty_screensaver: 1.2,
+            theme: GameTheme::Monokai.name().to_string(),
+            render_mode: RenderMode::Classic,
+            reduced_motion: false,
+            max_fps: 60,
+            mouse_control: false,
+            key_bindings: KeyBindings::default(),
+        }
+    }
+}
+
+impl Config {
+    /// `$XDG_CONFIG_HOME/terminal-pong/config.toml` (or the platform equivalent).
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join(APP_DIR_NAME).join(CONFIG_FILE_NAME))
+    }
+
+    /// Custom theme files live in a `themes` directory next to the config file.
+    pub fn themes_dir(config_path: &Path) -> PathBuf {
+        config_path
+            .parent()
+            .unwrap_or(Path::new("."))
+            .join(THEMES_DIR_NAME)
+    }
+
+    /// Reads the config at `path`. A missing file is not an error and yields
+    /// the defaults.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(format!("Could not read {}: {}", path.display(), e)),
+        };
+
+        toml::from_str(&contents)
+            .map_err(|e| format!("Invalid config {}: {}", path.display(), e.message().trim()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Could not create {}: {}", parent.display(), e))?;
+        }
+        let contents = toml::to_string_pretty(self)
+            .map_err(|e| format!("Could not serialize settings: {}", e))?;
+        fs::write(path, contents).map_err(|e| format!("Could not write {}: {}", path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a config path in an empty directory of its own for each test
+    fn temp_config(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "terminal-pong-config-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir.join(CONFIG_FILE_NAME)
+    }
+
+    #[test]
+    fn missing_file_gives_the_defaults() {
+        let path = temp_config("missing");
+        let config = Config::load(&path).unwrap();
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+        assert_eq!(config.theme, Config::default().theme);
+        assert_eq!(config.max_fps, Config::default().max_fps);
+    }
+
+    #[test]
+    fn unknown_and_missing_keys_are_tolerated() {
+        let path = temp_config("partial");
+        fs::write(
+            &path,
+            "theme = \"Nord\"\nfrom_a_newer_version = 3\n\n[key_bindings]\npause = [\"f1\"]\n",
+        )
+        .unwrap();
+        let config = Config::load(&path).unwrap();
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+        assert_eq!(config.theme, "Nord");
+        assert_eq!(config.render_mode, RenderMode::Classic);
+        assert_eq!(config.key_bindings.quit, KeyBindings::default().quit);
+    }
+
+    #[test]
+    fn parse_errors_are_reported() {
+        let path = temp_config("broken");
+        fs::write(&path, "theme = [broken\n").unwrap();
+        let result = Config::load(&path);
+        fs::write(&path, "max_fps = \"fast\"\n").unwrap();
+        let wrong_type = Config::load(&path);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+        assert!(result.unwrap_err().starts_with("Invalid config"));
+        assert!(wrong_type.is_err());
+    }
+
+    #[test]
+    fn saved_settings_load_back() {
+        let path = temp_config("round-trip");
+        let config = Config {
+            theme: "Gruvbox Dark".to_string(),
+            render_mode: RenderMode::Braille,
+            reduced_motion: true,
+            ..Config::default()
+        };
+        config.save(&path).unwrap();
+        let loaded = Config::load(&path).unwrap();
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+        assert_eq!(loaded.theme, config.theme);
+        assert_eq!(loaded.render_mode, RenderMode::Braille);
+        assert!(loaded.reduced_motion);
+        assert_eq!(loaded.key_bindings, config.key_bindings);
+    }
+}
@@ -0,0 +1,170 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use rand::Rng;
+use ratatui::{
+    layout::{Position, Rect},
+    style::Color,
+    Frame,
+};
+
+use crate::color::{self, ColorSupport};
+
+const TRAIL_LENGTH: usize = 6;
+const POWERED_TRAIL_LENGTH: usize = 10;
+const PARTICLE_LIFETIME: Duration = Duration::from_millis(450);
+const SHAKE_DURATION: Duration = Duration::from_millis(350);
+
+#[derive(Debug)]
+struct Particle {
+    x: f32,
+    y: f32,
+    velocity: (f32, f32), // cells per second
+    born: Instant,
+    color: Color,
+}
+
+/// Purely cosmetic extras drawn on top of the court: a fading ball trail,
+/// particle bursts and a border shake on scoring. The game only tells it what
+/// happened; nothing here feeds back into the physics, and it draws from its
+/// own random numbers so seeded matches play out the same either way.
+#[derive(Debug, Default)]
+pub struct Effects {
+    trail: VecDeque<([u16; 2], bool)>, // newest first, with `is_powered`
+    particles: Vec<Particle>,
+    shake_started: Option<Instant>,
+}
+
+impl Effects {
+    pub fn record_ball(&mut self, position: [u16; 2], is_powered: bool) {
+        self.trail.push_front((position, is_powered));
+        self.trail.truncate(POWERED_TRAIL_LENGTH);
+    }
+
+    /// Sprays `count` particles out of a point on the court.
+    pub fn burst(&mut self, position: [u16; 2], count: usize, color: Color) {
+        let mut rng = rand::rng();
+        let now = Instant::now();
+        for _ in 0..count {
+            let angle = rng.random_range(0.0..std::f32::consts::TAU);
+            let speed = rng.random_range(6.0..18.0);
+            self.particles.push(Particle {
+                x: position[0] as f32 + 1.0,
+                y: position[1] as f32,
+                // cells are about twice as tall as they are wide
+                velocity: (angle.cos() * speed, angle.sin() * speed / 2.0),
+                born: now,
+                color,
+            });
+        }
+    }
+
+    // the ball jumps back to the middle after a point, so the trail restarts
+    pub fn score(&mut self) {
+        self.trail.clear();
+        self.shake_started = Some(Instant::now());
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    fn shake_progress(&self) -> Option<f32> {
+        let elapsed = self.shake_started?.elapsed();
+        (elapsed < SHAKE_DURATION).then(|| elapsed.as_secs_f32() / SHAKE_DURATION.as_secs_f32())
+    }
+
+    /// How far to nudge the court border sideways this frame.
+    pub fn border_offset(&self) -> i16 {
+        match self.shake_progress() {
+            // alternate sides every ~50ms, settling down towards the end
+            Some(progress) if progress < 0.8 => {
+                let step = (progress * SHAKE_DURATION.as_millis() as f32 / 50.0) as i16;
+                if step % 2 == 0 {
+                    1
+                } else {
+                    -1
+                }
+            }
+            _ => 0,
+        }
+    }
+
+    /// Whether anything is still fading or moving.
+    pub fn is_active(&self) -> bool {
+        !self.particles.is_empty() || self.is_flashing()
+    }
+
+    pub fn is_flashing(&self) -> bool {
+        self.shake_progress().is_some()
+    }
+
+    /// Draws the trail and particles inside `area`, the court's inner area.
+    /// Colors fade from `ball` (or `power` for a powered ball) towards
+    /// `background` before being mapped to what the terminal supports.
+    pub fn draw(
+        &mut self,
+        frame: &mut Frame,
+        area: Rect,
+        [ball, power, background]: [Color; 3],
+        color_support: ColorSupport,
+    ) {
+        self.particles
+            .retain(|particle| particle.born.elapsed() < PARTICLE_LIFETIME);
+
+        let fade = |color: Color, amount: f32| {
+            color_support.map(color::blend(color, background, amount.clamp(0.0, 1.0)))
+        };
+        let buffer = frame.buffer_mut();
+        let mut put = |x: f32, y: f32, symbol: &str, color: Color| {
+            if x < 0.0 || y < 0.0 || x >= area.width as f32 || y >= area.height as f32 {
+                return;
+            }
+            let position = Position::new(area.x + x as u16, area.y + y as u16);
+            if let Some(cell) = buffer.cell_mut(position) {
+                cell.set_symbol(symbol).set_fg(color);
+            }
+        };
+
+        // skip the newest entry, that's where the ball itself is drawn
+        for (i, (position, is_powered)) in self.trail.iter().enumerate().skip(1) {
+            let length = if *is_powered {
+                POWERED_TRAIL_LENGTH
+            } else {
+                TRAIL_LENGTH
+            };
+            if i >= length {
+                break;
+            }
+            let amount = i as f32 / length as f32;
+            let (symbol, color) = if *is_powered {
+                (["█", "▓", "▒", "░"][(amount * 4.0) as usize], power)
+            } else {
+                (["▓", "▒", "░", "·"][(amount * 4.0) as usize], ball)
+            };
+            // the ball is two cells wide
+            for dx in 0..2 {
+                put(
+                    (position[0] + dx) as f32,
+                    position[1] as f32,
+                    symbol,
+                    fade(color, amount),
+                );
+            }
+        }
+
+        for particle in self.particles.iter() {
+            let age = particle.born.elapsed().as_secs_f32();
+            let amount = age / PARTICLE_LIFETIME.as_secs_f32();
+            let symbol = if amount < 0.5 { "•" } else { "·" };
+            put(
+                particle.x + particle.velocity.0 * age,
+                particle.y + particle.velocity.1 * age,
+                symbol,
+                fade(particle.color, amount),
+            );
+        }
+    }
+}
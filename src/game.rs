@@ -1,23 +1,38 @@
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, MouseEvent, MouseEventKind};
-use rand::random;
+use crossterm::event::{
+    self, Event, KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use std::{
+    collections::VecDeque,
     io,
     time::{Duration, Instant},
 };
 
+use tui_big_text::{BigText, PixelSize};
+use unicode_width::UnicodeWidthStr;
+
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Flex, Layout, Rect},
-    style::Style,
-    widgets::{Block, BorderType, Borders, Paragraph},
+    layout::{Alignment, Constraint, Flex, Layout, Margin, Position, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::Line,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
     Frame,
 };
 
 use crate::{
-    game_theme::GameTheme,
-    helpers::{centered_rect, string_to_char_array},
+    bindings_editor::{BindingsAction, BindingsEditor},
+    color::ColorSupport,
+    effects::Effects,
+    game_theme::{Theme, ThemeColors},
+    helpers::{centered_rect, is_key_press, truncate_to_width},
+    hires::{self, RenderMode, Shape},
+    keybindings::{Action, KeyBindings},
+    menu::{ItemKind, Menu, MenuEvent, MenuItem, MenuStyle},
 };
 
-pub const PLAYER_NAME_CHAR_LEN: usize = 16;
+// names are limited by both characters and terminal columns
+pub const PLAYER_NAME_MAX_GRAPHEMES: usize = 16;
+pub const PLAYER_NAME_MAX_WIDTH: usize = 24;
 const DEFAULT_BAR_LENGTH: u8 = 5;
 const DEFAULT_BALL_VELOCITY_X: i8 = 3;
 const DEFAULT_BALL_VELOCITY_Y: i8 = 1;
@@ -25,6 +40,66 @@ const DEFAULT_PADDLE_WIDTH: u16 = 3;
 const STARTING_POWER_MOVES: u8 = 10;
 const DEFAULT_DIFFICULTY: f32 = 1.0;
 
+// smallest terminal the court (plus a one-line controls bar) still fits in
+pub const MIN_TERMINAL_WIDTH: u16 = 60;
+pub const MIN_TERMINAL_HEIGHT: u16 = 20;
+// past this the ball takes too long to cross and the AI can't keep up
+const MAX_COURT_WIDTH: u16 = 200;
+const MAX_COURT_HEIGHT: u16 = 50;
+// below this the controls shrink to a single unbordered line
+const COMPACT_HEIGHT: u16 = 28;
+// inner height of the original fixed 130x28 court, where paddles were 5 tall
+const CLASSIC_INNER_HEIGHT: u16 = 26;
+const DEFAULT_MAX_FPS: u32 = 60;
+// added to every tick: the old loop waited on a 5ms input poll after each
+// tick, and the ball speed and AI reactions are tuned to that slower pace
+const TICK_PACE_MS: u64 = 5;
+// after a stall, run at most this many missed ticks before giving up on them
+const MAX_CATCH_UP_TICKS: u32 = 5;
+// how long to sleep when nothing on screen is moving
+const IDLE_WAIT: Duration = Duration::from_millis(250);
+// fastest a mouse-driven paddle catches up with the pointer
+const MOUSE_CELLS_PER_TICK: u16 = 2;
+// the 3-2-1 before serving
+const COUNTDOWN: Duration = Duration::from_secs(3);
+const MIN_BAR_LENGTH: u8 = 3;
+const MAX_BAR_LENGTH: u8 = 9;
+
+/// Splits the terminal into the court and the controls bar below it. The
+/// court grows with the terminal up to a cap, and small terminals get a
+/// one-line controls bar instead of a bordered one.
+pub fn court_layout(area: Rect) -> (Rect, Rect) {
+    let controls_height = if area.height < COMPACT_HEIGHT + 3 {
+        1
+    } else {
+        3
+    };
+    let width = area.width.min(MAX_COURT_WIDTH);
+    let height = area
+        .height
+        .saturating_sub(controls_height)
+        .min(MAX_COURT_HEIGHT);
+
+    let [column] = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [court, controls] = Layout::vertical([
+        Constraint::Length(height),
+        Constraint::Length(controls_height),
+    ])
+    .flex(Flex::Center)
+    .areas(column);
+    (court, controls)
+}
+
+// paddles keep roughly the same share of the court height at any size
+fn bar_length_for(inner_height: u16) -> u8 {
+    let length = (inner_height as u32 * DEFAULT_BAR_LENGTH as u32
+        + CLASSIC_INNER_HEIGHT as u32 / 2)
+        / CLASSIC_INNER_HEIGHT as u32;
+    (length as u8).clamp(MIN_BAR_LENGTH, MAX_BAR_LENGTH)
+}
+
 #[derive(Debug, Clone, Copy)]
 struct ComputerAI {
     reaction_delay: f32,     // Time before reacting to ball direction change
@@ -35,13 +110,12 @@ struct ComputerAI {
     current_speed: f32,      // Current movement speed (with acceleration)
     target_position: f32,    // Where the AI wants to move
     // difficulty: f32,         // 0.0 to 1.0, affects all parameters
-    fatigue: f32,         // Increases over time, affects performance
-    last_update: Instant, // For delta time calculations
+    fatigue: f32, // Increases over time, affects performance
 }
 
 #[derive(Debug, Default)]
 pub struct Player {
-    pub name: [char; PLAYER_NAME_CHAR_LEN],
+    pub name: String,
     pub score: u32,
 
     pub power_moves_left: u8,
@@ -49,42 +123,221 @@ pub struct Player {
 
     pub bar_position: u16,
     pub bar_length: u8,
+    previous_bar_position: u16, // before the last tick, for smooth drawing
 
     pub is_computer: bool,
     computer_ai: Option<ComputerAI>,
+    paddle_color: Option<Color>, // from the player's profile, over the theme's
+}
+
+impl Player {
+    pub fn power_moves_used(&self) -> u8 {
+        STARTING_POWER_MOVES - self.power_moves_left
+    }
 }
 
 #[derive(Debug, Default)]
 struct Ball {
     position: [u16; 2],
+    previous: [u16; 2], // position before the last tick, for smooth drawing
     velocity: [i8; 2],
     is_powered: bool,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum GameType {
     AgainstAi,
     ScreenSaver,
     WithFriend,
 }
 
+#[derive(Debug, Clone, Copy)]
+enum PauseAction {
+    Resume,
+    Restart,
+    SwapSides,
+    Difficulty,
+    Theme,
+    ReducedMotion,
+    KeyBindings,
+    ReturnToMenu,
+    Quit,
+}
+
+// pause menu entries that are opened directly
+const PAUSE_RETURN_TO_MENU: usize = 4;
+
 #[derive(Debug)]
 pub struct Game {
     game_type: GameType,
     players: [Player; 2],
     ball: Ball,
     game_area: Rect,
-    last_update: Instant,
+    last_update: Instant, // when the last simulation tick was due
+    frame_interval: Duration,
+    last_draw: Instant,
+    needs_redraw: bool,
     is_paused: bool,
+    countdown_until: Option<Instant>, // play is held until then
+    pause_menu: Menu<PauseAction>,
+    bindings_editor: Option<BindingsEditor>, // opened from the pause menu
+    show_debug: bool,
+    bindings: KeyBindings,
+    // with key release events, held movement keys move paddles every tick
+    // instead of relying on the terminal's key repeat
+    key_release_events: bool,
+    held_keys: Vec<Action>,
+    // player 1's paddle follows the pointer and a click is a power move
+    mouse_control: bool,
+    mouse_target: Option<u16>, // pointer row in court coordinates
+    // player 1's controls drive the right paddle after swapping sides
+    sides_swapped: bool,
+    // the last second of ticks and frames, for the debug overlay's rates
+    tick_times: VecDeque<Instant>,
+    frame_times: VecDeque<Instant>,
     scored_keep_display: bool,
     difficulty: f32,
     should_exit: bool,
-    theme: GameTheme,
+    quit_requested: bool, // leave the whole app, not just the match
+    themes: Vec<Theme>,
+    theme: usize,
+    color_support: ColorSupport,
+    render_mode: RenderMode,
+    effects: Effects,
+    reduced_motion: bool,
+    rng: StdRng,
+    first_to: Option<u32>,
+    winner: Option<usize>,
+    rally: u32, // paddle hits since the last serve
+    longest_rally: u32,
 }
 
 impl Game {
-    pub fn set_theme(&mut self, theme: GameTheme) {
-        self.theme = theme;
+    // `themes` is what the pause menu cycles through, starting at `selected`
+    pub fn set_theme(&mut self, themes: Vec<Theme>, selected: usize) {
+        self.theme = selected.min(themes.len().saturating_sub(1));
+        self.themes = themes;
+    }
+
+    pub fn set_color_support(&mut self, color_support: ColorSupport) {
+        self.color_support = color_support;
+    }
+
+    // caps how often the court is redrawn, separately from the tick rate
+    pub fn set_max_fps(&mut self, max_fps: u32) {
+        self.frame_interval = Duration::from_secs(1) / max_fps.max(1);
+    }
+
+    pub fn set_key_release_events(&mut self, enabled: bool) {
+        self.key_release_events = enabled;
+        self.held_keys.clear();
+    }
+
+    pub fn set_key_bindings(&mut self, bindings: KeyBindings) {
+        self.bindings = bindings;
+        self.held_keys.clear();
+    }
+
+    pub fn set_mouse_control(&mut self, enabled: bool) {
+        self.mouse_control = enabled;
+        self.mouse_target = None;
+    }
+
+    pub fn set_render_mode(&mut self, render_mode: RenderMode) {
+        self.render_mode = render_mode;
+    }
+
+    // turns off trails, particles and the score shake
+    pub fn set_reduced_motion(&mut self, reduced_motion: bool) {
+        self.reduced_motion = reduced_motion;
+        self.effects.clear();
+    }
+
+    fn theme(&self) -> &Theme {
+        &self.themes[self.theme]
+    }
+
+    fn colors(&self) -> ThemeColors {
+        self.theme().colors.for_support(self.color_support)
+    }
+
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    // the match ends once a player reaches this many points
+    pub fn set_first_to(&mut self, points: Option<u32>) {
+        self.first_to = points;
+    }
+
+    // holds play for the 3-2-1, there's none when just watching
+    fn start_countdown(&mut self) {
+        if self.game_type != GameType::ScreenSaver {
+            self.countdown_until = Some(Instant::now() + COUNTDOWN);
+            self.needs_redraw = true;
+        }
+    }
+
+    fn is_counting_down(&self) -> bool {
+        self.countdown_until
+            .is_some_and(|until| Instant::now() < until)
+    }
+
+    /// Pauses a match in progress, e.g. when the terminal got too small to
+    /// play in. The screensaver just keeps going.
+    pub fn auto_pause(&mut self) {
+        if !self.is_paused && self.winner.is_none() && self.game_type != GameType::ScreenSaver {
+            self.pause();
+        }
+    }
+
+    /// The theme as it was left, possibly changed from the pause menu.
+    pub fn theme_index(&self) -> usize {
+        self.theme
+    }
+
+    pub fn reduced_motion(&self) -> bool {
+        self.reduced_motion
+    }
+
+    /// The bindings as they were left, possibly changed from the pause menu.
+    pub fn key_bindings(&self) -> &KeyBindings {
+        &self.bindings
+    }
+
+    /// The player whose controls and profile are player `slot`'s, on
+    /// whichever side they ended up.
+    pub fn player_for_slot(&self, slot: usize) -> &Player {
+        &self.players[self.side_of(slot)]
+    }
+
+    /// Sets player `slot`'s paddle color; `None` uses the theme's.
+    pub fn set_paddle_color(&mut self, slot: usize, color: Option<Color>) {
+        let side = self.side_of(slot);
+        self.players[side].paddle_color = color;
+    }
+
+    /// The player slot that won the match, if it was played to the end.
+    pub fn winning_slot(&self) -> Option<usize> {
+        self.winner.map(|side| self.side_of(side))
+    }
+
+    pub fn game_type(&self) -> GameType {
+        self.game_type
+    }
+
+    pub fn difficulty(&self) -> f32 {
+        self.difficulty
+    }
+
+    /// Most paddle hits in a row during the match.
+    pub fn longest_rally(&self) -> u32 {
+        self.longest_rally
+    }
+
+    /// Whether the match ended because the player chose to quit the game.
+    pub fn quit_requested(&self) -> bool {
+        self.quit_requested
     }
 }
 
@@ -95,8 +348,6 @@ impl Game {
         game_type: GameType,
         difficulty: Option<f32>,
     ) -> Self {
-        let theme = GameTheme::Monokai;
-
         let final_difficulty = difficulty.unwrap_or(DEFAULT_DIFFICULTY).clamp(0.0, 2.0);
         let ai_player = ComputerAI {
             reaction_delay: 0.2 + (2.0 - final_difficulty) * 0.5, // 0.2-0.7 seconds
@@ -107,37 +358,40 @@ impl Game {
             current_speed: 0.0,
             target_position: 0.0,
             fatigue: 0.0,
-            last_update: Instant::now(),
         };
 
         let player1 = Player {
-            name: string_to_char_array(player_names[0]),
+            name: player_names[0].to_string(),
             bar_position: (game_area.height / 2).saturating_sub((DEFAULT_BAR_LENGTH / 2) as u16),
             bar_length: DEFAULT_BAR_LENGTH,
-            is_computer: false,
+            previous_bar_position: 0,
+            is_computer: game_type == GameType::ScreenSaver,
             computer_ai: if game_type == GameType::ScreenSaver {
-                Some(ai_player.clone())
+                Some(ai_player)
             } else {
                 None
             },
             power_moves_left: STARTING_POWER_MOVES,
             last_power_used_at: None,
             score: 0,
+            paddle_color: None,
         };
 
         let player2 = Player {
-            name: string_to_char_array(player_names[1]),
+            name: player_names[1].to_string(),
             bar_position: (game_area.height / 2).saturating_sub((DEFAULT_BAR_LENGTH / 2) as u16),
             bar_length: DEFAULT_BAR_LENGTH,
-            is_computer: false,
+            previous_bar_position: 0,
+            is_computer: game_type == GameType::AgainstAi || game_type == GameType::ScreenSaver,
             computer_ai: if game_type == GameType::AgainstAi || game_type == GameType::ScreenSaver {
-                Some(ai_player.clone())
+                Some(ai_player)
             } else {
                 None
             },
             power_moves_left: STARTING_POWER_MOVES,
             last_power_used_at: None,
             score: 0,
+            paddle_color: None,
         };
 
         Self {
@@ -148,16 +402,43 @@ impl Game {
                     game_area.width.saturating_sub(4) / 2,
                     game_area.height.saturating_sub(4) / 2,
                 ],
+                previous: [0, 0],
                 velocity: [DEFAULT_BALL_VELOCITY_X, DEFAULT_BALL_VELOCITY_Y],
                 is_powered: false,
             },
             last_update: Instant::now(),
-            game_area: game_area,
+            frame_interval: Duration::from_secs(1) / DEFAULT_MAX_FPS,
+            last_draw: Instant::now(),
+            needs_redraw: true,
+            game_area,
             is_paused: false,
+            countdown_until: None,
+            pause_menu: Menu::new(Vec::new()),
+            bindings_editor: None,
+            show_debug: false,
+            bindings: KeyBindings::default(),
+            key_release_events: false,
+            held_keys: Vec::new(),
+            mouse_control: false,
+            mouse_target: None,
+            sides_swapped: false,
+            tick_times: VecDeque::new(),
+            frame_times: VecDeque::new(),
             scored_keep_display: false,
             difficulty: final_difficulty,
             should_exit: false,
-            theme,
+            quit_requested: false,
+            themes: Theme::builtin(),
+            theme: 0,
+            color_support: ColorSupport::TrueColor,
+            render_mode: RenderMode::Classic,
+            effects: Effects::default(),
+            reduced_motion: false,
+            rng: StdRng::from_os_rng(),
+            first_to: None,
+            winner: None,
+            rally: 0,
+            longest_rally: 0,
         }
     }
 
@@ -165,8 +446,67 @@ impl Game {
         self.game_area
     }
 
+    /// Moves the court to `game_area`. When its size changes, the ball and
+    /// paddles are scaled to keep their relative positions and the paddles
+    /// are resized to fit the new height.
     pub fn set_area(&mut self, game_area: Rect) {
+        let old_area = self.game_area;
         self.game_area = game_area;
+        if old_area == game_area {
+            return;
+        }
+        self.needs_redraw = true;
+        if old_area.width == game_area.width && old_area.height == game_area.height {
+            return;
+        }
+
+        let (old_width, old_height) = Self::court_size(old_area);
+        let (width, height) = Self::court_size(game_area);
+        if width == 0 || height == 0 {
+            return;
+        }
+        // the match starts once there's a court to show it on
+        if old_height == 0 {
+            self.start_countdown();
+        }
+        // scales a coordinate, or centers it on the first placement
+        let scale = |value: f32, old: u16, new: u16| {
+            if old == 0 {
+                new as f32 / 2.0
+            } else {
+                value * new as f32 / old as f32
+            }
+        };
+
+        let bar_length = bar_length_for(height);
+        for player in self.players.iter_mut() {
+            let center = player.bar_position as f32 + player.bar_length as f32 / 2.0;
+            let center = scale(center, old_height, height);
+            player.bar_length = bar_length;
+            player.bar_position = (center - bar_length as f32 / 2.0)
+                .round()
+                .clamp(0.0, height.saturating_sub(bar_length as u16) as f32)
+                as u16;
+            player.previous_bar_position = player.bar_position;
+        }
+
+        let ball = &mut self.ball;
+        let x = scale(ball.position[0] as f32, old_width, width).round() as u16;
+        let y = scale(ball.position[1] as f32, old_height, height).round() as u16;
+        ball.position = [
+            x.clamp(
+                DEFAULT_PADDLE_WIDTH,
+                width.saturating_sub(DEFAULT_PADDLE_WIDTH + 1),
+            ),
+            y.min(height.saturating_sub(1)),
+        ];
+        ball.previous = ball.position;
+    }
+
+    // the space the ball and paddles move in, in the same coordinates as
+    // `Ball::position` and `Player::bar_position`
+    fn court_size(area: Rect) -> (u16, u16) {
+        (area.width.saturating_sub(3), area.height.saturating_sub(2))
     }
 
     pub fn get_player(&self, index: usize) -> &Player {
@@ -193,45 +533,125 @@ impl Game {
             }
         } else {
             // down
-            let inner_height = self.game_area.height.saturating_sub(2);
+            let (_, inner_height) = Self::court_size(self.game_area);
             if player.bar_position + (player.bar_length as u16) < inner_height {
                 player.bar_position += step;
             }
         }
     }
 
+    // the paddle a player's controls move, 0 for player 1's keys
+    fn side_of(&self, controls: usize) -> usize {
+        if self.sides_swapped {
+            1 - controls
+        } else {
+            controls
+        }
+    }
+
+    // one cell per tick for each movement key that's held down
+    fn move_held_paddles(&mut self) {
+        for i in 0..self.held_keys.len() {
+            if let Some((player, direction)) = self.held_keys[i].movement() {
+                self.move_player(self.side_of(player), direction);
+            }
+        }
+    }
+
+    // eases player 1's paddle towards the pointer, centered on its row
+    fn move_mouse_paddle(&mut self) {
+        let Some(target) = self.mouse_target else {
+            return;
+        };
+        let side = self.side_of(0);
+        let player = &self.players[side];
+        let center = player.bar_position + player.bar_length as u16 / 2;
+        let direction = match target.cmp(&center) {
+            std::cmp::Ordering::Less => 1,
+            std::cmp::Ordering::Greater => -1,
+            std::cmp::Ordering::Equal => return,
+        };
+        for _ in 0..target.abs_diff(center).min(MOUSE_CELLS_PER_TICK) {
+            self.move_player(side, direction);
+        }
+    }
+
     fn handle_key_event(&mut self, key_event: KeyEvent) {
-        match key_event.code {
-            KeyCode::Esc => self.should_exit = true,
-            KeyCode::Char('q') => self.should_exit = true,
-            KeyCode::Char('p') => self.toggle_pause(),
-            // player 1
-            KeyCode::Char('/') => self.power_move(0),
-            KeyCode::Up => self.move_player(0, 1),
-            KeyCode::Down => self.move_player(0, -1),
-            // player 2
-            KeyCode::Char(' ') => self.power_move(1),
-            KeyCode::Char('w') => self.move_player(1, 1),
-            KeyCode::Char('s') => self.move_player(1, -1),
+        let Some(action) = self.bindings.action_for(key_event.code) else {
+            return;
+        };
+        if let Some((player, direction)) = action.movement() {
+            match key_event.kind {
+                KeyEventKind::Press if self.key_release_events => {
+                    if !self.held_keys.contains(&action) {
+                        self.held_keys.push(action);
+                    }
+                }
+                KeyEventKind::Release => self.held_keys.retain(|held| *held != action),
+                // without release events every press or repeat is one step
+                KeyEventKind::Press => self.move_player(self.side_of(player), direction),
+                KeyEventKind::Repeat => {}
+            }
+            return;
+        }
+        if key_event.kind != KeyEventKind::Press {
+            return;
+        }
+
+        match action {
+            // there's nothing to lose when just watching
+            Action::Quit if self.game_type == GameType::ScreenSaver => self.should_exit = true,
+            Action::Quit => {
+                self.pause();
+                self.pause_menu.open(PAUSE_RETURN_TO_MENU);
+            }
+            Action::Pause => self.pause(),
+            Action::Debug => self.show_debug = !self.show_debug,
+            Action::P1Power => self.power_move(self.side_of(0)),
+            Action::P2Power => self.power_move(self.side_of(1)),
             _ => {}
         }
     }
 
     fn handle_mouse_event(&mut self, mouse_event: MouseEvent) {
         match mouse_event.kind {
-            MouseEventKind::ScrollUp => self.move_player(0, 1),
-            MouseEventKind::ScrollDown => self.move_player(0, -1),
-            _ => {}
+            MouseEventKind::ScrollUp => self.move_player(self.side_of(0), 1),
+            MouseEventKind::ScrollDown => self.move_player(self.side_of(0), -1),
+            _ if !self.mouse_control || self.players[self.side_of(0)].computer_ai.is_some() => {}
+            kind => {
+                let court = self.game_area.inner(Margin::new(1, 1));
+                let position = Position::new(mouse_event.column, mouse_event.row);
+                if !court.contains(position) {
+                    // the paddle stays put once the pointer leaves the court
+                    self.mouse_target = None;
+                    return;
+                }
+                match kind {
+                    MouseEventKind::Moved | MouseEventKind::Drag(_) => {
+                        self.mouse_target = Some(position.y - court.y);
+                    }
+                    MouseEventKind::Down(MouseButton::Left) => {
+                        self.mouse_target = Some(position.y - court.y);
+                        self.power_move(self.side_of(0));
+                    }
+                    _ => {}
+                }
+            }
         }
     }
 
     fn handle_events(&mut self) -> io::Result<()> {
         // Process all pending events for better responsiveness
-        while event::poll(Duration::from_millis(5))? {
+        self.needs_redraw = true;
+        while event::poll(Duration::ZERO)? {
             match event::read()? {
                 Event::Mouse(mouse_event) => self.handle_mouse_event(mouse_event),
-                Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
-                    self.handle_key_event(key_event)
+                Event::Key(key_event) => self.handle_key_event(key_event),
+                // releases can't be seen once the window is in the background
+                Event::FocusLost => {
+                    self.held_keys.clear();
+                    self.mouse_target = None;
+                    self.auto_pause();
                 }
                 _ => {}
             }
@@ -239,44 +659,198 @@ impl Game {
         Ok(())
     }
 
-    // key events while paused (pause/options popup)
+    fn pause_items(&self) -> Vec<MenuItem<PauseAction>> {
+        let action = |label: &str, action| MenuItem::new(label, ItemKind::Action(action));
+        let keep_playing = || MenuItem::new("Keep Playing", ItemKind::Back);
+        vec![
+            action("Resume", PauseAction::Resume),
+            action("Restart Match", PauseAction::Restart),
+            action("Swap Sides", PauseAction::SwapSides),
+            MenuItem::new(
+                "Settings",
+                ItemKind::SubMenu(vec![
+                    MenuItem::new(
+                        "Difficulty",
+                        ItemKind::Slider {
+                            id: PauseAction::Difficulty,
+                            value: self.difficulty,
+                            min: 0.0,
+                            max: 2.0,
+                            step: 0.1,
+                        },
+                    ),
+                    MenuItem::new(
+                        "Theme",
+                        ItemKind::Choice {
+                            id: PauseAction::Theme,
+                            options: self.themes.iter().map(|theme| theme.name.clone()).collect(),
+                            selected: self.theme,
+                        },
+                    ),
+                    MenuItem::new(
+                        "Effects",
+                        ItemKind::Choice {
+                            id: PauseAction::ReducedMotion,
+                            options: vec!["On".to_string(), "Reduced".to_string()],
+                            selected: self.reduced_motion as usize,
+                        },
+                    ),
+                    action("Key Bindings…", PauseAction::KeyBindings),
+                    MenuItem::back(),
+                ]),
+            ),
+            // PAUSE_RETURN_TO_MENU
+            MenuItem::new(
+                "Return to Main Menu",
+                ItemKind::SubMenu(vec![
+                    keep_playing(),
+                    action("Leave This Match", PauseAction::ReturnToMenu),
+                ]),
+            ),
+            MenuItem::new(
+                "Quit",
+                ItemKind::SubMenu(vec![
+                    keep_playing(),
+                    action("Quit terminal.pong", PauseAction::Quit),
+                ]),
+            ),
+        ]
+    }
+
+    fn apply_pause_action(&mut self, menu_event: MenuEvent<PauseAction>) {
+        match menu_event {
+            MenuEvent::Activate(PauseAction::Resume) | MenuEvent::Back => self.resume(),
+            MenuEvent::Activate(PauseAction::Restart) => {
+                self.restart();
+                self.resume();
+            }
+            MenuEvent::Activate(PauseAction::SwapSides) => {
+                self.swap_sides();
+                self.resume();
+            }
+            MenuEvent::Slider(PauseAction::Difficulty, value) => self.difficulty = value,
+            MenuEvent::Choice(PauseAction::Theme, index) => self.theme = index,
+            MenuEvent::Choice(PauseAction::ReducedMotion, index) => {
+                self.set_reduced_motion(index == 1)
+            }
+            MenuEvent::Activate(PauseAction::KeyBindings) => {
+                self.bindings_editor = Some(BindingsEditor::new(&self.bindings));
+            }
+            MenuEvent::Activate(PauseAction::ReturnToMenu) => self.should_exit = true,
+            MenuEvent::Activate(PauseAction::Quit) => {
+                self.quit_requested = true;
+                self.should_exit = true;
+            }
+            _ => {}
+        }
+        self.pause_menu.set_items(self.pause_items());
+    }
+
+    // key and mouse events while the pause menu is open
     fn handle_pause_events(&mut self) -> io::Result<()> {
-        while event::poll(Duration::from_millis(5))? {
-            match event::read()? {
-                Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
-                    match key_event.code {
-                        KeyCode::Char('p') => self.is_paused = false, // Resume
-                        KeyCode::Enter => self.is_paused = false,     // Resume
-                        KeyCode::Esc => self.should_exit = true,
-                        KeyCode::Char('d') => {
-                            // Cycle through all available themes
-                            self.theme = match self.theme {
-                                GameTheme::Monokai => GameTheme::Solarized,
-                                GameTheme::Solarized => GameTheme::Dracula,
-                                GameTheme::Dracula => GameTheme::GruvboxDark,
-                                GameTheme::GruvboxDark => GameTheme::Nord,
-                                GameTheme::Nord => GameTheme::OneDark,
-                                GameTheme::OneDark => GameTheme::HighContrast,
-                                GameTheme::HighContrast => GameTheme::Monokai,
-                            };
+        self.needs_redraw = true;
+        while event::poll(Duration::ZERO)? {
+            let menu_event = match event::read()? {
+                Event::Key(key_event) if is_key_press(&key_event) => {
+                    if let Some(editor) = self.bindings_editor.as_mut() {
+                        if let BindingsAction::Close = editor.handle_key(key_event) {
+                            self.bindings = editor.bindings.clone();
+                            self.bindings_editor = None;
                         }
-                        KeyCode::Left => {
-                            self.difficulty = (self.difficulty - 0.1).clamp(0.0, 2.0);
+                        continue;
+                    }
+                    let is_toggle = |code| {
+                        self.bindings.is_bound(Action::Pause, code)
+                            || self.bindings.is_bound(Action::Debug, code)
+                    };
+                    match key_event.code {
+                        // holding a toggle shouldn't flip it back and forth
+                        code if key_event.kind == KeyEventKind::Repeat && is_toggle(code) => {
+                            continue
                         }
-                        KeyCode::Right => {
-                            self.difficulty = (self.difficulty + 0.1).clamp(0.0, 2.0);
+                        code if self.bindings.is_bound(Action::Pause, code) => MenuEvent::Back,
+                        code if self.bindings.is_bound(Action::Debug, code) => {
+                            self.show_debug = !self.show_debug;
+                            continue;
                         }
-                        _ => {}
+                        _ => self.pause_menu.handle_key(key_event),
                     }
                 }
-                _ => {}
+                Event::Mouse(mouse_event) if self.bindings_editor.is_none() => {
+                    self.pause_menu.handle_mouse(mouse_event)
+                }
+                _ => continue,
+            };
+            self.apply_pause_action(menu_event);
+            if !self.is_paused || self.should_exit {
+                break;
             }
         }
         Ok(())
     }
 
-    fn toggle_pause(&mut self) {
-        self.is_paused = !self.is_paused;
+    // key events once the match is decided
+    fn handle_game_over_events(&mut self) -> io::Result<()> {
+        self.needs_redraw = true;
+        while event::poll(Duration::ZERO)? {
+            if let Event::Key(key_event) = event::read()? {
+                if is_key_press(&key_event)
+                    && (matches!(key_event.code, KeyCode::Enter | KeyCode::Esc)
+                        || self.bindings.is_bound(Action::Quit, key_event.code))
+                {
+                    self.should_exit = true;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn pause(&mut self) {
+        self.is_paused = true;
+        // releases while paused go to the pause menu
+        self.held_keys.clear();
+        self.mouse_target = None;
+        self.pause_menu.set_items(self.pause_items());
+        self.pause_menu.reset();
+    }
+
+    fn resume(&mut self) {
+        self.is_paused = false;
+        self.bindings_editor = None;
+        self.start_countdown();
+    }
+
+    // a fresh match with the same players, court and settings
+    fn restart(&mut self) {
+        let (width, height) = Self::court_size(self.game_area);
+        for player in self.players.iter_mut() {
+            player.score = 0;
+            player.power_moves_left = STARTING_POWER_MOVES;
+            player.last_power_used_at = None;
+            player.bar_position = (height / 2).saturating_sub(player.bar_length as u16 / 2);
+            player.previous_bar_position = player.bar_position;
+        }
+        self.ball = Ball {
+            position: [width / 2, height / 2],
+            previous: [width / 2, height / 2],
+            velocity: [DEFAULT_BALL_VELOCITY_X, DEFAULT_BALL_VELOCITY_Y],
+            is_powered: false,
+        };
+        self.scored_keep_display = false;
+        self.winner = None;
+        self.rally = 0;
+        self.longest_rally = 0;
+        self.effects.clear();
+    }
+
+    // the players trade paddles, keeping their names, scores and controls
+    fn swap_sides(&mut self) {
+        self.players.swap(0, 1);
+        self.sides_swapped = !self.sides_swapped;
+        self.ball.velocity[0] = -self.ball.velocity[0];
+        let (width, _) = Self::court_size(self.game_area);
+        self.ball.position[0] = width.saturating_sub(self.ball.position[0]);
+        self.ball.previous = self.ball.position;
     }
 
     /**
@@ -285,8 +859,7 @@ impl Game {
      * None -> no collision, ball position updated normally
      */
     fn update_ball_position(&mut self) -> Option<u8> {
-        let inner_width = self.game_area.width.saturating_sub(3);
-        let inner_height = self.game_area.height.saturating_sub(2);
+        let (inner_width, inner_height) = Self::court_size(self.game_area);
 
         let players = &self.players;
         let ball = &mut self.ball;
@@ -304,34 +877,34 @@ impl Game {
 
         if !self.scored_keep_display {
             // ball collision with Player 1's bar (left side)
-            if new_x <= DEFAULT_PADDLE_WIDTH && ball.velocity[0] < 0 {
-                if new_y >= players[0].bar_position
-                    && new_y < players[0].bar_position + players[0].bar_length as u16
-                {
-                    ball.velocity[0] = -ball.velocity[0];
-                    ball.position[0] = DEFAULT_PADDLE_WIDTH;
-                    return Some(1);
-                }
+            if new_x <= DEFAULT_PADDLE_WIDTH
+                && ball.velocity[0] < 0
+                && new_y >= players[0].bar_position
+                && new_y < players[0].bar_position + players[0].bar_length as u16
+            {
+                ball.velocity[0] = -ball.velocity[0];
+                ball.position[0] = DEFAULT_PADDLE_WIDTH;
+                return Some(1);
             }
 
             // ball collision with Player 2's bar (right side)
-            if new_x >= inner_width - DEFAULT_PADDLE_WIDTH - 1 && ball.velocity[0] > 0 {
-                if new_y >= players[1].bar_position
-                    && new_y < players[1].bar_position + players[1].bar_length as u16
-                {
-                    ball.velocity[0] = -DEFAULT_BALL_VELOCITY_X;
-                    ball.position[0] = inner_width - DEFAULT_PADDLE_WIDTH - 1;
-                    ball.is_powered = false;
-                    return Some(2);
-                }
+            if new_x >= inner_width - DEFAULT_PADDLE_WIDTH - 1
+                && ball.velocity[0] > 0
+                && new_y >= players[1].bar_position
+                && new_y < players[1].bar_position + players[1].bar_length as u16
+            {
+                ball.velocity[0] = -DEFAULT_BALL_VELOCITY_X;
+                ball.position[0] = inner_width - DEFAULT_PADDLE_WIDTH - 1;
+                ball.is_powered = false;
+                return Some(2);
             }
         }
 
         // ball went off screen (reset)
         if new_x < DEFAULT_PADDLE_WIDTH || new_x > inner_width - DEFAULT_PADDLE_WIDTH - 1 {
-            if new_x <= 0 || new_x >= inner_width {
+            if new_x == 0 || new_x >= inner_width {
                 // Ball exited the screen: left or right
-                if new_x <= 0 {
+                if new_x == 0 {
                     // ball exited on the left → player missed → computer scores
                     self.players[1].score += 1;
                 } else {
@@ -342,10 +915,11 @@ impl Game {
                 // reset ball to center
                 ball.position = [
                     inner_width / 2,
-                    rand::random_range(1..inner_height.saturating_sub(1)),
+                    self.rng.random_range(1..inner_height.saturating_sub(1)),
                 ];
+                ball.previous = ball.position;
 
-                let random_number: i16 = rand::random_range(0..=1);
+                let random_number: i16 = self.rng.random_range(0..=1);
                 let direction = if random_number == 0 { 1 } else { -1 };
 
                 ball.velocity[0] = direction * DEFAULT_BALL_VELOCITY_X;
@@ -366,7 +940,36 @@ impl Game {
         None
     }
 
-    fn update_computer_player(&mut self, player_index: usize) {
+    // the column the AI expects to meet the ball at
+    fn paddle_x(game_area: Rect, player_index: usize) -> u16 {
+        if player_index == 0 {
+            DEFAULT_PADDLE_WIDTH // Player 1's paddle is on the left
+        } else {
+            game_area.width - DEFAULT_PADDLE_WIDTH // Player 2's paddle is on the right
+        }
+    }
+
+    // where the ball will cross `paddle_x`, following its bounces off the top
+    // and bottom walls
+    fn predict_intercept(ball: &Ball, paddle_x: u16, inner_height: u16) -> f32 {
+        let time_to_paddle_x =
+            (paddle_x as f32 - ball.position[0] as f32) / ball.velocity[0] as f32;
+        let mut pred_y = ball.position[1] as f32 + ball.velocity[1] as f32 * time_to_paddle_x;
+
+        // simulate top and bottom wall bounces
+        while pred_y < 0.0 || pred_y > inner_height as f32 {
+            if pred_y < 0.0 {
+                pred_y = -pred_y; // bounce off top wall
+            } else {
+                pred_y = 2.0 * inner_height as f32 - pred_y; // bounce off bottom wall
+            }
+        }
+        pred_y
+    }
+
+    // `dt` is one tick in seconds, so the AI plays the same however the
+    // ticks are spread out in real time
+    fn update_computer_player(&mut self, player_index: usize, dt: f32) {
         let computer = &mut self.players[player_index];
         let ball = &self.ball;
 
@@ -375,16 +978,9 @@ impl Game {
         }
         let ai = computer.computer_ai.as_mut().unwrap();
 
-        let inner_height = self.game_area.height;
-        let paddle_x = if player_index == 0 {
-            DEFAULT_PADDLE_WIDTH // Player 1's paddle is on the left
-        } else {
-            self.game_area.width - DEFAULT_PADDLE_WIDTH // Player 2's paddle is on the right
-        };
+        let (_, inner_height) = Self::court_size(self.game_area);
+        let paddle_x = Self::paddle_x(self.game_area, player_index);
 
-        // accumulate delta time to add fatigue
-        let dt = ai.last_update.elapsed().as_secs_f32();
-        ai.last_update = Instant::now();
         // increase fatigue over time
         if self.game_type == GameType::ScreenSaver {
             ai.fatigue = (ai.fatigue + dt * 0.001).min(0.05); // much less fatigue for AI vs AI
@@ -422,18 +1018,7 @@ impl Game {
             // active/predictive positioning
             // "predict" ball position with wall bounces
 
-            let time_to_paddle_x =
-                (paddle_x as f32 - ball.position[0] as f32) / ball.velocity[0] as f32;
-            let mut pred_y = ball.position[1] as f32 + ball.velocity[1] as f32 * time_to_paddle_x;
-
-            // simulate top and bottom wall bounces
-            while pred_y < 0.0 || pred_y > inner_height as f32 {
-                if pred_y < 0.0 {
-                    pred_y = -pred_y; // bounce off top wall
-                } else {
-                    pred_y = 2.0 * inner_height as f32 - pred_y; // bounce off bottom wall
-                }
-            }
+            let mut pred_y = Self::predict_intercept(ball, paddle_x, inner_height);
 
             // sprinkle some prediction errors -,-
             let (error_magnitude, oops_chance, random_chance) = match self.game_type {
@@ -444,17 +1029,17 @@ impl Game {
                     0.1,
                 ),
             };
-            let prediction_error = (random::<f32>() - 0.5) * error_magnitude;
+            let prediction_error = (self.rng.random::<f32>() - 0.5) * error_magnitude;
             pred_y += prediction_error;
 
             // make big oopsies occasionally
-            if random::<f32>() < oops_chance {
-                pred_y += (random::<f32>() - 0.5) * 3.0;
+            if self.rng.random::<f32>() < oops_chance {
+                pred_y += (self.rng.random::<f32>() - 0.5) * 3.0;
             }
 
             // add some final randomness
-            if random::<f32>() < random_chance {
-                pred_y += (random::<f32>() - 0.5) * 1.0;
+            if self.rng.random::<f32>() < random_chance {
+                pred_y += (self.rng.random::<f32>() - 0.5) * 1.0;
             }
 
             // clamp to fix
@@ -475,8 +1060,8 @@ impl Game {
 
         // add some jitter and behavioral quirks
         let jitter = match self.game_type {
-            GameType::ScreenSaver => (random::<f32>() - 0.5) * 0.02 * (1.0 + ai.fatigue), // much less jitter
-            GameType::AgainstAi => (random::<f32>() - 0.5) * 0.1 * (1.0 + ai.fatigue),
+            GameType::ScreenSaver => (self.rng.random::<f32>() - 0.5) * 0.02 * (1.0 + ai.fatigue), // much less jitter
+            GameType::AgainstAi => (self.rng.random::<f32>() - 0.5) * 0.1 * (1.0 + ai.fatigue),
             _ => 0.0,
         };
         let movement = distance_to_target.signum() * ai.current_speed + jitter;
@@ -484,9 +1069,9 @@ impl Game {
         let final_movement = match self.game_type {
             GameType::ScreenSaver => movement, // no hesitation/overshoot in screensaver
             GameType::AgainstAi => {
-                if random::<f32>() < 0.01 + ai.fatigue * 0.02 {
+                if self.rng.random::<f32>() < 0.01 + ai.fatigue * 0.02 {
                     movement * 0.3 // less hesitation
-                } else if random::<f32>() < 0.015 {
+                } else if self.rng.random::<f32>() < 0.015 {
                     movement * 1.2 // less overshoot
                 } else {
                     movement
@@ -498,17 +1083,27 @@ impl Game {
         // apply new position with clamping
         let new_pos = (paddle_center + final_movement).clamp(
             computer.bar_length as f32 / 2.0,
-            (inner_height - computer.bar_length as u16) as f32,
+            inner_height as f32 - computer.bar_length as f32 / 2.0,
         );
 
-        computer.bar_position = (new_pos - computer.bar_length as f32 / 2.0) as u16;
+        // rounded, truncating would nudge the paddle up on every small move
+        computer.bar_position = (new_pos - computer.bar_length as f32 / 2.0).round() as u16;
+    }
+
+    // how many columns in front of a paddle a power move can be used in
+    fn power_window(&self) -> u16 {
+        let min_range = 4.0;
+        let max_range = 12.0;
+        let allowed_range = (max_range - min_range) * (1.0 - self.difficulty) + min_range;
+        allowed_range.round() as u16
     }
 
     fn power_move(&mut self, player_index: usize) {
+        let allowed_range = self.power_window();
         let player = &mut self.players[player_index];
 
-        if player.power_moves_left <= 0 {
-            return; // no power move left
+        if player.is_computer || player.power_moves_left == 0 {
+            return; // no power moves for the AI, or none left
         }
 
         let ball = &mut self.ball;
@@ -522,11 +1117,6 @@ impl Game {
         let within_bar = ball.position[1] >= player.bar_position
             && ball.position[1] < player.bar_position + player.bar_length as u16;
 
-        let min_range = 4.0;
-        let max_range = 12.0;
-        let allowed_range = (max_range - min_range) * (1.0 - self.difficulty) + min_range;
-        let allowed_range = allowed_range.round() as u16;
-
         let within_x = if player_index == 0 {
             ball.position[0] > 1 && ball.position[0] < 1 + allowed_range
         } else {
@@ -544,9 +1134,128 @@ impl Game {
         }
     }
 
+    // paddles flash right after a power move; without colors they flash by
+    // swapping to reverse video instead
+    fn paddle_style(&self, player: &Player, colors: &ThemeColors) -> Style {
+        let is_flashing = Self::is_flashing(player);
+
+        if self.color_support.is_monochrome() {
+            if is_flashing {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default()
+            }
+        } else {
+            let bar = self.bar_color(player, colors);
+            let fill = if is_flashing {
+                colors.player_bar_power
+            } else {
+                bar
+            };
+            Style::default().fg(bar).bg(fill)
+        }
+    }
+
+    // a profile's own paddle color, or the theme's
+    fn bar_color(&self, player: &Player, colors: &ThemeColors) -> Color {
+        player
+            .paddle_color
+            .map_or(colors.player_bar, |color| self.color_support.map(color))
+    }
+
+    fn is_flashing(player: &Player) -> bool {
+        player
+            .last_power_used_at
+            .is_some_and(|last| last.elapsed() < Duration::from_millis(200))
+    }
+
+    // how far along the next tick is, for drawing between two ticks
+    fn tick_progress(&self) -> f64 {
+        (self.last_update.elapsed().as_secs_f64() / self.tick_interval().as_secs_f64()).min(1.0)
+    }
+
+    // the ball between its last two positions
+    fn ball_draw_position(&self) -> (f64, f64) {
+        let progress = self.tick_progress();
+        let lerp = |from: u16, to: u16| from as f64 + (to as f64 - from as f64) * progress;
+        (
+            lerp(self.ball.previous[0], self.ball.position[0]),
+            lerp(self.ball.previous[1], self.ball.position[1]),
+        )
+    }
+
+    // computer paddles move once per tick, so they're smoothed like the ball;
+    // human ones follow the keyboard directly
+    fn bar_draw_position(&self, player: &Player) -> f64 {
+        if player.computer_ai.is_none() {
+            return player.bar_position as f64;
+        }
+        let (from, to) = (
+            player.previous_bar_position as f64,
+            player.bar_position as f64,
+        );
+        from + (to - from) * self.tick_progress()
+    }
+
+    // the half-block canvas can't paint without a color, so monochrome
+    // terminals keep the classic look for it
+    fn uses_canvas(&self) -> bool {
+        match self.render_mode {
+            // the canvas can only flash a paddle by color, which monochrome
+            // doesn't have, so it keeps the outlined classic paddles
+            _ if self.color_support.is_monochrome() => false,
+            RenderMode::Classic => false,
+            RenderMode::HalfBlock | RenderMode::Braille => true,
+        }
+    }
+
+    // paddles and ball on a sub-cell canvas
+    fn draw_hires_elements(&self, frame: &mut Frame, inner_area: Rect) {
+        let colors = self.colors();
+        let (ball_x, ball_y) = self.ball_draw_position();
+
+        let paddle = |player: &Player, x: f64| Shape {
+            x,
+            y: self.bar_draw_position(player),
+            width: DEFAULT_PADDLE_WIDTH as f64,
+            height: player.bar_length as f64,
+            color: if Self::is_flashing(player) {
+                colors.player_bar_power
+            } else {
+                self.bar_color(player, &colors)
+            },
+            round: false,
+        };
+        // the same columns as the classic paddles, which the ball bounces off
+        let right_paddle_x = inner_area.width.saturating_sub(DEFAULT_PADDLE_WIDTH) as f64;
+        let shapes = [
+            paddle(self.get_player(0), 0.0),
+            paddle(self.get_player(1), right_paddle_x),
+            Shape {
+                x: ball_x,
+                y: ball_y,
+                width: 2.0,
+                height: 1.0,
+                color: colors.ball,
+                round: true,
+            },
+        ];
+        hires::draw_court(
+            frame,
+            inner_area,
+            self.render_mode,
+            colors.background,
+            &shapes,
+        );
+    }
+
     fn draw_core_elements(&self, frame: &mut Frame) {
-        let colors = self.theme.colors();
+        let colors = self.colors();
         let game_area = self.get_area();
+        if self.uses_canvas() {
+            self.draw_hires_elements(frame, game_area.inner(Margin::new(1, 1)));
+            return;
+        }
         let inner_area = Rect::new(
             game_area.x + 1,
             game_area.y + 1,
@@ -558,50 +1267,33 @@ impl Game {
         let player1 = self.get_player(0);
         let bar_1_area = Rect::new(
             inner_area.x,
-            inner_area.y + player1.bar_position,
+            inner_area.y + self.bar_draw_position(player1).round() as u16,
             3,
             player1.bar_length as u16,
         );
-        let bar_1_color = if let Some(last) = player1.last_power_used_at {
-            if last.elapsed() < Duration::from_millis(200) {
-                colors.player_bar_power
-            } else {
-                colors.player_bar
-            }
-        } else {
-            colors.player_bar
-        };
         let bar_1 = Block::default()
             .borders(Borders::ALL)
-            .style(Style::default().fg(colors.player_bar).bg(bar_1_color));
+            .style(self.paddle_style(player1, &colors));
         frame.render_widget(bar_1, bar_1_area);
 
         // Player 2 bar (right side)
         let player2 = self.get_player(1);
         let bar_2_area = Rect::new(
             inner_area.x + inner_area.width - 4,
-            inner_area.y + player2.bar_position,
+            inner_area.y + self.bar_draw_position(player2).round() as u16,
             3,
             player2.bar_length as u16,
         );
-        let bar_2_color = if let Some(last) = player2.last_power_used_at {
-            if last.elapsed() < Duration::from_millis(200) {
-                colors.player_bar_power
-            } else {
-                colors.player_bar
-            }
-        } else {
-            colors.player_bar
-        };
         let bar_2 = Block::default()
             .borders(Borders::ALL)
-            .style(Style::default().fg(colors.player_bar).bg(bar_2_color));
+            .style(self.paddle_style(player2, &colors));
         frame.render_widget(bar_2, bar_2_area);
 
         // Ball
+        let (ball_x, ball_y) = self.ball_draw_position();
         let ball_area = Rect::new(
-            inner_area.x + self.ball.position[0],
-            inner_area.y + self.ball.position[1],
+            inner_area.x + ball_x.round() as u16,
+            inner_area.y + ball_y.round() as u16,
             2,
             2,
         );
@@ -610,157 +1302,671 @@ impl Game {
     }
 
     pub fn draw(&mut self, frame: &mut Frame) {
+        self.last_draw = Instant::now();
+        self.needs_redraw = false;
+        Self::record_time(&mut self.frame_times);
         let area = frame.area();
-        let colors = self.theme.colors();
+        let colors = self.colors();
 
-        let main_layout = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints(vec![Constraint::Length(130)])
-            .flex(Flex::Center)
-            .split(area);
-
-        let layout = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints(vec![
-                Constraint::Length(28), // game block
-                Constraint::Length(3),  // controls block
-            ])
-            .flex(Flex::Center)
-            .split(main_layout[0]);
-
-        let game_area = layout[0];
+        let (game_area, controls_area) = court_layout(area);
         self.set_area(game_area);
 
+        // the border shakes and flashes for a moment after a point
+        let border_area = Rect {
+            x: game_area
+                .x
+                .saturating_add_signed(self.effects.border_offset())
+                .min(area.right().saturating_sub(game_area.width)),
+            ..game_area
+        };
+        let border_color = if self.effects.is_flashing() {
+            colors.ball
+        } else {
+            colors.border
+        };
         let title = self.get_block_title("terminal.pong");
         let block = Block::default()
             .title(title)
             .borders(Borders::ALL)
             .border_type(BorderType::Thick)
-            .style(Style::default().fg(colors.border).bg(colors.background))
+            .style(Style::default().fg(border_color).bg(colors.background))
             .title_alignment(Alignment::Center);
-        frame.render_widget(block, game_area);
+        frame.render_widget(block, border_area);
+
+        // effects go under the paddles and ball and never touch game state
+        if !self.reduced_motion {
+            let theme_colors = self.theme().colors;
+            self.effects.draw(
+                frame,
+                game_area.inner(Margin::new(1, 1)),
+                [
+                    theme_colors.ball,
+                    theme_colors.player_bar_power,
+                    theme_colors.background,
+                ],
+                self.color_support,
+            );
+        }
 
         self.draw_core_elements(frame);
 
-        let controls_text = " Player 1: ↑/↓ or mouse wheel, '/'=Power    |    Player 2: W/S, Space=Power    |    P=Pause    |    Esc=Quit ";
-        let controls = Paragraph::new(controls_text)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .border_type(BorderType::Rounded)
-                    .style(Style::default().fg(colors.border)),
-            )
+        let key = |action| self.bindings.label(action);
+        let controls = if controls_area.height < 3 {
+            Paragraph::new(format!(
+                "P1 {}/{} {}  ·  P2 {}/{} {}  ·  {} pause  ·  {} quit",
+                key(Action::P1Up),
+                key(Action::P1Down),
+                key(Action::P1Power),
+                key(Action::P2Up),
+                key(Action::P2Down),
+                key(Action::P2Power),
+                key(Action::Pause),
+                key(Action::Quit),
+            ))
+        } else {
+            Paragraph::new(format!(
+                " Player 1: {}/{} or {}, {}=Power    |    Player 2: {}/{}, {}=Power    |    {}=Pause    |    {}=Quit ",
+                key(Action::P1Up),
+                key(Action::P1Down),
+                if self.mouse_control {
+                    "mouse"
+                } else {
+                    "mouse wheel"
+                },
+                key(Action::P1Power),
+                key(Action::P2Up),
+                key(Action::P2Down),
+                key(Action::P2Power),
+                key(Action::Pause),
+                key(Action::Quit),
+            ))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Rounded)
+                        .style(Style::default().fg(colors.border)),
+                )
+        };
+        let controls = controls
             .style(Style::default().fg(colors.text))
             .alignment(Alignment::Center);
-        frame.render_widget(controls, layout[1]);
+        frame.render_widget(controls, controls_area);
+
+        if self.show_debug {
+            self.draw_debug_overlay(frame, &colors);
+        }
 
         if self.is_paused {
-            // draw pause/options popup if paused
-            let popup_width = 52;
-            let popup_height = 12;
-            let popup_area = centered_rect(popup_width, popup_height, area.width, area.height);
+            self.draw_pause_menu(frame, area, &colors);
+        } else if self.winner.is_none() {
+            self.draw_countdown(frame, game_area, &colors);
+        }
+
+        if let Some(winner) = self.winner {
+            let popup_area = centered_rect(52, 9, area.width, area.height);
             let popup_block = Block::default()
-                .title("Paused - Options")
+                .title(Line::from("Game Over").style(Style::default().fg(colors.title)))
                 .borders(Borders::ALL)
                 .border_type(BorderType::Double)
                 .style(Style::default().fg(colors.accent))
                 .title_alignment(Alignment::Center);
             frame.render_widget(popup_block, popup_area);
 
-            // options and instructions
-            let diff_label = match self.difficulty {
-                d if d < 0.6 => "Easy",
-                d if d < 1.3 => "Normal",
-                _ => "Hard",
-            };
-            let theme_label = match self.theme {
-                GameTheme::Monokai => "Monokai",
-                GameTheme::Solarized => "Solarized",
-                GameTheme::Dracula => "Dracula",
-                GameTheme::GruvboxDark => "Gruvbox Dark",
-                GameTheme::Nord => "Nord",
-                GameTheme::OneDark => "One Dark",
-                GameTheme::HighContrast => "High Contrast",
-            };
-            let options_text = format!(
-                "\n  Difficulty: {} ({:.2})\n [←/→] Adjust  [D] Toggle Theme (Current: {})\n  [P/Enter] Resume  [Esc] Quit\n",
-                diff_label, self.difficulty, theme_label
+            let result_text = format!(
+                "\n{} wins!\nFinal Score: {} - {}\n\n[Enter/Esc] Back to menu",
+                self.players[winner].name, self.players[0].score, self.players[1].score
             );
-            let options = Paragraph::new(options_text)
+            let result = Paragraph::new(result_text)
                 .style(Style::default().fg(colors.text))
                 .alignment(Alignment::Center);
-            let options_area = Rect::new(
+            let result_area = Rect::new(
                 popup_area.x + 2,
-                popup_area.y + 2,
+                popup_area.y + 1,
                 popup_area.width - 4,
-                popup_area.height - 4,
+                popup_area.height - 2,
             );
-            frame.render_widget(options, options_area);
+            frame.render_widget(result, result_area);
         }
     }
 
-    pub fn game_loop(&mut self) -> io::Result<bool> {
-        // If paused, only handle pause menu events
-        if self.is_paused {
-            self.handle_pause_events()?;
-            if self.should_exit {
-                return Ok(false);
-            }
-            return Ok(true);
+    // tells the effects layer what the last tick did, judged from the outside
+    // so the physics stays unaware of it
+    fn add_effects(&mut self, hit: Option<u8>, velocity_y: i8, total_score: u32) {
+        let colors = self.theme().colors;
+        let ball = &self.ball;
+        if self.players[0].score + self.players[1].score != total_score {
+            self.effects.score();
+            return;
         }
 
-        // FPS/speed is relative to difficulty
+        self.effects.record_ball(ball.position, ball.is_powered);
+        if let Some(player) = hit {
+            let player = &self.players[player as usize - 1];
+            let color = if Self::is_flashing(player) {
+                colors.player_bar_power
+            } else {
+                player.paddle_color.unwrap_or(colors.player_bar)
+            };
+            self.effects.burst(ball.position, 10, color);
+        } else if ball.velocity[1] != velocity_y {
+            self.effects.burst(ball.position, 5, colors.accent);
+        }
+    }
+
+    // FPS/speed is relative to difficulty
+    fn tick_interval(&self) -> Duration {
         let min_fps = 15.0;
         let max_fps = 40.0;
         let fps = min_fps + (max_fps - min_fps) * self.difficulty;
-        let each_frame = (1000.0 / fps).round() as u64;
+        Duration::from_millis((1000.0 / fps).round() as u64 + TICK_PACE_MS)
+    }
+
+    // one fixed simulation step
+    fn tick(&mut self) {
+        let dt = self.tick_interval().as_secs_f32();
+        self.move_held_paddles();
+        self.move_mouse_paddle();
+        if self.game_type == GameType::AgainstAi {
+            // the AI may be on either side once the players swap
+            self.update_computer_player(0, dt);
+            self.update_computer_player(1, dt);
+        } else {
+            if self.rng.random() {
+                self.update_computer_player(0, dt);
+                self.update_computer_player(1, dt);
+            } else {
+                self.update_computer_player(1, dt);
+                self.update_computer_player(0, dt);
+            }
+        }
+        self.ball.previous = self.ball.position;
+        for player in self.players.iter_mut() {
+            player.previous_bar_position = player.bar_position;
+        }
+        let total_score = self.players[0].score + self.players[1].score;
+        let velocity_y = self.ball.velocity[1];
+        let hit = self.update_ball_position();
+        if !self.reduced_motion {
+            self.add_effects(hit, velocity_y, total_score);
+        }
+        if hit.is_some() {
+            self.rally += 1;
+            self.longest_rally = self.longest_rally.max(self.rally);
+        }
+        if self.players[0].score + self.players[1].score != total_score {
+            self.rally = 0;
+            self.start_countdown();
+        }
+
+        if let Some(points) = self.first_to {
+            self.winner = self.players.iter().position(|p| p.score >= points);
+        }
+        self.needs_redraw = true;
+        Self::record_time(&mut self.tick_times);
+    }
+
+    fn record_time(times: &mut VecDeque<Instant>) {
+        let now = Instant::now();
+        times.push_back(now);
+        while times
+            .front()
+            .is_some_and(|time| now.duration_since(*time) > Duration::from_secs(1))
+        {
+            times.pop_front();
+        }
+    }
+
+    // events per second over the last second
+    fn rate(times: &VecDeque<Instant>) -> f32 {
+        match (times.front(), times.back()) {
+            (Some(first), Some(last)) if times.len() > 1 => {
+                (times.len() - 1) as f32 / last.duration_since(*first).as_secs_f32()
+            }
+            _ => 0.0,
+        }
+    }
+
+    // the big 3-2-1 in the middle of the court
+    fn draw_countdown(&self, frame: &mut Frame, game_area: Rect, colors: &ThemeColors) {
+        let Some(remaining) = self
+            .countdown_until
+            .and_then(|until| until.checked_duration_since(Instant::now()))
+        else {
+            return;
+        };
+        let court = game_area.inner(Margin::new(1, 1));
+        let (pixel_size, height) = if court.height >= 16 {
+            (PixelSize::Full, 8)
+        } else {
+            (PixelSize::HalfHeight, 4)
+        };
+        let [number_area] = Layout::vertical([Constraint::Length(height)])
+            .flex(Flex::Center)
+            .areas(court);
+        let number = BigText::builder()
+            .pixel_size(pixel_size)
+            .style(Style::default().fg(colors.accent))
+            .lines(vec![remaining.as_secs_f32().ceil().to_string().into()])
+            .alignment(Alignment::Center)
+            .build();
+        frame.render_widget(number, number_area);
+    }
+
+    // the menu selection, as on the other screens
+    fn selection_style(&self, colors: &ThemeColors) -> Style {
+        let style = Style::default().fg(colors.selection).bold();
+        if self.color_support.is_monochrome() {
+            style.reversed()
+        } else {
+            style
+        }
+    }
+
+    fn match_rules(&self) -> String {
+        let target = match self.first_to {
+            Some(points) => format!("First to {}", points),
+            None => "Endless match".to_string(),
+        };
+        let powers = self
+            .players
+            .iter()
+            .map(|player| player.power_moves_left.to_string())
+            .collect::<Vec<_>>()
+            .join(" : ");
+        format!("{}  ·  Power moves left {}", target, powers)
+    }
+
+    fn draw_pause_menu(&mut self, frame: &mut Frame, area: Rect, colors: &ThemeColors) {
+        let selection = self.selection_style(colors);
+        if let Some(editor) = self.bindings_editor.as_ref() {
+            let popup_area = centered_rect(58, 18, area.width, area.height);
+            frame.render_widget(Clear, popup_area);
+            editor.draw(frame, popup_area, colors, selection);
+            return;
+        }
+
+        let popup_area = centered_rect(52, 18, area.width, area.height);
+        frame.render_widget(Clear, popup_area);
+        let sub_menus = self.pause_menu.open_sub_menus().len();
+        let title = if sub_menus == 0 {
+            "Paused".to_string()
+        } else {
+            format!("Paused › {}", self.pause_menu.open_sub_menus().join(" › "))
+        };
+        let popup_block = Block::default()
+            .title(Line::from(title).style(Style::default().fg(colors.title)))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .style(Style::default().fg(colors.accent))
+            .title_alignment(Alignment::Center);
+        let inner = popup_block.inner(popup_area);
+        frame.render_widget(popup_block, popup_area);
+
+        let [score_area, rules_area, menu_area, hint_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .margin(1)
+        .areas(inner);
+        let score = format!(
+            "{}  {} : {}  {}",
+            truncate_to_width(&self.players[0].name, 18),
+            self.players[0].score,
+            self.players[1].score,
+            truncate_to_width(&self.players[1].name, 18)
+        );
+        frame.render_widget(
+            Paragraph::new(score)
+                .style(Style::default().fg(colors.text).bold())
+                .alignment(Alignment::Center),
+            score_area,
+        );
+        frame.render_widget(
+            Paragraph::new(self.match_rules())
+                .style(Style::default().fg(colors.text))
+                .alignment(Alignment::Center),
+            rules_area,
+        );
+        let style = MenuStyle {
+            normal: Style::default().fg(colors.text),
+            selected: selection,
+            markers: true,
+        };
+        self.pause_menu.draw(frame, menu_area, &style);
+        frame.render_widget(
+            Paragraph::new(if sub_menus == 0 {
+                format!(
+                    "↑↓ select  Enter choose  {}/Esc resume",
+                    self.bindings.label(Action::Pause)
+                )
+            } else {
+                "↑↓ select  Enter choose  Esc back".to_string()
+            })
+            .style(Style::default().fg(colors.text))
+            .alignment(Alignment::Center),
+            hint_area,
+        );
+    }
 
-        if self.last_update.elapsed() >= Duration::from_millis(each_frame) {
-            self.handle_events()?;
-            if self.should_exit {
-                return Ok(false);
+    // tuning aid toggled with F3: rates, ball and AI state, the predicted
+    // intercept and the power-move window, drawn over the court
+    fn draw_debug_overlay(&self, frame: &mut Frame, colors: &ThemeColors) {
+        let inner = self.game_area.inner(Margin::new(1, 1));
+        let (inner_width, inner_height) = Self::court_size(self.game_area);
+        let marker_style = Style::default().fg(colors.accent).bg(colors.background);
+
+        // power-move window edges
+        let window = self.power_window();
+        let right_edge = self.game_area.width.saturating_sub(1);
+        for x in [1 + window, right_edge.saturating_sub(window)] {
+            if x >= inner.width {
+                continue;
+            }
+            for y in inner.top()..inner.bottom() {
+                frame.buffer_mut()[(inner.x + x, y)]
+                    .set_symbol("┊")
+                    .set_style(marker_style);
             }
-            if self.game_type == GameType::AgainstAi {
-                self.update_computer_player(1);
+        }
+
+        // where the ball will reach the paddle it's heading for
+        let approaching = if self.ball.velocity[0] < 0 { 0 } else { 1 };
+        let intercept = (self.ball.velocity[0] != 0).then(|| {
+            Self::predict_intercept(
+                &self.ball,
+                Self::paddle_x(self.game_area, approaching),
+                inner_height,
+            )
+        });
+        if let Some(y) = intercept {
+            let x = if approaching == 0 {
+                DEFAULT_PADDLE_WIDTH
             } else {
-                if rand::random() {
-                    self.update_computer_player(0);
-                    self.update_computer_player(1);
+                inner_width.saturating_sub(DEFAULT_PADDLE_WIDTH + 1)
+            };
+            let y = (y.round() as u16).min(inner.height.saturating_sub(1));
+            if x < inner.width {
+                frame.buffer_mut()[(inner.x + x, inner.y + y)]
+                    .set_symbol("◆")
+                    .set_style(Style::default().fg(colors.ball).bg(colors.background));
+            }
+        }
+
+        let mut lines = vec![
+            Line::from(format!(
+                "Ticks   {:>5.1}/s  target {:.1}/s",
+                Self::rate(&self.tick_times),
+                1.0 / self.tick_interval().as_secs_f32()
+            )),
+            Line::from(format!(
+                "Frames  {:>5.1}/s  cap {:.0}/s",
+                Self::rate(&self.frame_times),
+                1.0 / self.frame_interval.as_secs_f32()
+            )),
+            Line::from(format!(
+                "Ball    ({}, {})  v ({}, {}){}",
+                self.ball.position[0],
+                self.ball.position[1],
+                self.ball.velocity[0],
+                self.ball.velocity[1],
+                if self.ball.is_powered {
+                    "  powered"
                 } else {
-                    self.update_computer_player(1);
-                    self.update_computer_player(0);
+                    ""
                 }
+            )),
+            Line::from(format!("Power   {} cols", window)),
+            Line::from(match intercept {
+                Some(y) => format!("Heading P{} at y {:.1}", approaching + 1, y),
+                None => "Heading nowhere".to_string(),
+            }),
+        ];
+        for (i, player) in self.players.iter().enumerate() {
+            if let Some(ai) = player.computer_ai.as_ref() {
+                lines.push(Line::from(format!(
+                    "P{} AI   target {:.1}  react {:.2}s",
+                    i + 1,
+                    ai.target_position,
+                    ai.reaction_timer
+                )));
+                lines.push(Line::from(format!(
+                    "        fatigue {:.3}  speed {:.2}",
+                    ai.fatigue, ai.current_speed
+                )));
             }
-            let _ = self.update_ball_position();
+        }
+
+        let panel = Rect::new(
+            inner.x + DEFAULT_PADDLE_WIDTH + 1,
+            inner.y,
+            40.min(inner.width.saturating_sub(DEFAULT_PADDLE_WIDTH + 1)),
+            (lines.len() as u16 + 2).min(inner.height),
+        );
+        frame.render_widget(Clear, panel);
+        frame.render_widget(
+            Paragraph::new(lines)
+                .block(
+                    Block::default()
+                        .title(Line::from("Debug").style(Style::default().fg(colors.title)))
+                        .borders(Borders::ALL)
+                        .style(Style::default().fg(colors.border)),
+                )
+                .style(Style::default().fg(colors.text).bg(colors.background)),
+            panel,
+        );
+    }
 
+    // anything on screen that changes without a tick or an input
+    fn is_animating(&self) -> bool {
+        let is_playing = !self.is_paused && self.winner.is_none();
+        is_playing
+            || (!self.reduced_motion && self.effects.is_active())
+            || self.players.iter().any(Self::is_flashing)
+    }
+
+    // when the loop next has something to do if no input arrives first
+    fn next_deadline(&self) -> Instant {
+        let next_frame = self.last_draw + self.frame_interval;
+        let next_wake = if self.needs_redraw || self.is_animating() {
+            next_frame
+        } else {
+            Instant::now() + IDLE_WAIT
+        };
+        if self.is_paused || self.winner.is_some() || self.is_counting_down() {
+            next_wake
+        } else {
+            next_wake.min(self.last_update + self.tick_interval())
+        }
+    }
+
+    /// Whether a frame is due: something changed and the render cap allows it.
+    pub fn should_draw(&self) -> bool {
+        (self.needs_redraw || self.is_animating())
+            && self.last_draw.elapsed() >= self.frame_interval
+    }
+
+    pub fn game_loop(&mut self) -> io::Result<bool> {
+        // sleep until input arrives or the next tick or frame is due
+        let timeout = self
+            .next_deadline()
+            .saturating_duration_since(Instant::now());
+        if event::poll(timeout)? {
+            if self.winner.is_some() {
+                self.handle_game_over_events()?;
+            } else if self.is_paused {
+                // If paused, only handle pause menu events
+                self.handle_pause_events()?;
+            } else {
+                self.handle_events()?;
+            }
+        }
+        if self.should_exit {
+            return Ok(false);
+        }
+
+        // nothing to simulate until the court has been placed, and paused
+        // time or a countdown shouldn't pile up as ticks to catch up on
+        if self.is_paused
+            || self.winner.is_some()
+            || self.is_counting_down()
+            || Self::court_size(self.game_area).1 == 0
+        {
             self.last_update = Instant::now();
+            self.ball.previous = self.ball.position;
+            for player in self.players.iter_mut() {
+                player.previous_bar_position = player.bar_position;
+            }
+            return Ok(true);
+        }
+
+        // fixed timestep: run every tick that's due, however often we draw
+        let tick = self.tick_interval();
+        let mut ticks = 0;
+        while self.last_update.elapsed() >= tick
+            && self.winner.is_none()
+            && !self.is_counting_down()
+        {
+            if ticks == MAX_CATCH_UP_TICKS {
+                self.last_update = Instant::now();
+                break;
+            }
+            self.tick();
+            self.last_update += tick;
+            ticks += 1;
         }
 
         Ok(true)
     }
 
     fn get_block_title(&self, app_name: &'static str) -> String {
-        let player1 = self.get_player(0);
-        let mut player_text = player1.name.iter().collect::<String>();
-        player_text += &format!("({})", player1.score);
+        let names = [0, 1].map(|i| self.get_player(i).name.as_str());
+        let scores = [0, 1].map(|i| format!("({})", self.get_player(i).score));
+        let mut texts = [0, 1].map(|i| format!("{}{}", names[i], scores[i]));
+
+        // leave some of the court's border showing on either side
+        let width = (self.game_area.width as usize * 3 / 4).saturating_sub(2);
+        let names_width = |texts: &[String; 2]| texts[0].width() + texts[1].width();
+
+        // " p1 ─── app ─── p2 ", dropping the app name when it doesn't fit
+        let fixed = names_width(&texts) + app_name.width() + 6;
+        if fixed + 2 <= width {
+            let dashes = width - fixed;
+            return format!(
+                " {} {} {} {} {} ",
+                texts[0],
+                "─".repeat(dashes / 2),
+                app_name,
+                "─".repeat(dashes - dashes / 2),
+                texts[1],
+            );
+        }
+        // " p1 ─ p2 " at the least, shortening long names to make room
+        let room = width.saturating_sub(5);
+        if names_width(&texts) > room {
+            texts = [0, 1].map(|i| {
+                let name_room = (room / 2).saturating_sub(scores[i].width());
+                format!("{}{}", truncate_to_width(names[i], name_room), scores[i])
+            });
+        }
+        let dashes = width.saturating_sub(names_width(&texts) + 4).max(1);
+        format!(" {} {} {} ", texts[0], "─".repeat(dashes), texts[1])
+    }
+}
 
-        let player2 = self.get_player(1);
-        let mut computer_text = player2.name.iter().collect::<String>();
-        computer_text += &format!("({})", player2.score);
-
-        let padding_left: u16 = 32;
-        let padding_right: u16 = 32;
-
-        let total_padding = padding_left + padding_right + app_name.len() as u16;
-        let available_space = 130 - total_padding;
-
-        format!(
-            " {} {} {} {} {} ",
-            player_text.trim_end(),
-            "─".repeat((available_space / 2) as usize),
-            app_name.trim_end(),
-            "─".repeat((available_space / 2) as usize),
-            computer_text.trim_start(),
-        )
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_game(game_type: GameType) -> Game {
+        Game::new(["Ana", "Bo"], Rect::default(), game_type, None)
+    }
+
+    fn paddle_center(player: &Player) -> f32 {
+        player.bar_position as f32 + player.bar_length as f32 / 2.0
+    }
+
+    #[test]
+    fn court_fills_small_terminals_and_caps_large_ones() {
+        let (court, controls) =
+            court_layout(Rect::new(0, 0, MIN_TERMINAL_WIDTH, MIN_TERMINAL_HEIGHT));
+        assert_eq!(court, Rect::new(0, 0, 60, 19));
+        assert_eq!(controls, Rect::new(0, 19, 60, 1));
+
+        let (court, controls) = court_layout(Rect::new(0, 0, 300, 80));
+        assert_eq!(
+            (court.width, court.height),
+            (MAX_COURT_WIDTH, MAX_COURT_HEIGHT)
+        );
+        assert_eq!(court.x, 50);
+        assert_eq!(controls.height, 3);
+        assert_eq!(controls.y, court.bottom());
+    }
+
+    #[test]
+    fn paddles_keep_their_share_of_the_height() {
+        assert_eq!(bar_length_for(CLASSIC_INNER_HEIGHT), DEFAULT_BAR_LENGTH);
+        assert_eq!(bar_length_for(52), 10.min(MAX_BAR_LENGTH));
+        assert_eq!(bar_length_for(1), MIN_BAR_LENGTH);
+        assert_eq!(bar_length_for(u16::MAX), MAX_BAR_LENGTH);
+    }
+
+    #[test]
+    fn first_placement_centers_everything() {
+        let mut game = new_game(GameType::AgainstAi);
+        assert!(!game.is_counting_down());
+
+        game.set_area(Rect::new(0, 0, 103, 32));
+        let (width, height) = Game::court_size(game.game_area);
+        assert_eq!((width, height), (100, 30));
+        for player in &game.players {
+            assert_eq!(player.bar_length, bar_length_for(height));
+            assert!((paddle_center(player) - 15.0).abs() <= 0.5);
+        }
+        assert_eq!(game.ball.position, [50, 15]);
+        assert!(game.is_counting_down());
+    }
+
+    #[test]
+    fn resizing_scales_ball_and_paddles() {
+        let mut game = new_game(GameType::WithFriend);
+        game.set_area(Rect::new(0, 0, 103, 32));
+        game.ball.position = [25, 6];
+        game.players[0].bar_position = 0;
+        game.players[1].bar_position = 30 - game.players[1].bar_length as u16;
+
+        game.set_area(Rect::new(0, 0, 203, 52));
+        assert_eq!(game.ball.position, [50, 10]);
+        assert_eq!(game.ball.previous, game.ball.position);
+        let bar_length = bar_length_for(50);
+        assert_eq!(game.players[0].bar_length, bar_length);
+        // centers keep their share of the height: 3 of 30 rows, 27 of 30
+        assert!((paddle_center(&game.players[0]) - 5.0).abs() <= 0.5);
+        assert!((paddle_center(&game.players[1]) - 45.0).abs() <= 0.5);
+
+        // and back again
+        game.set_area(Rect::new(0, 0, 103, 32));
+        assert_eq!(game.ball.position, [25, 6]);
+        assert!((paddle_center(&game.players[1]) - 27.0).abs() <= 0.5);
+    }
+
+    #[test]
+    fn smallest_court_keeps_everything_inside() {
+        let mut game = new_game(GameType::WithFriend);
+        game.set_area(Rect::new(0, 0, 203, 52));
+        game.ball.position = [198, 49];
+        game.players[1].bar_position = 50 - game.players[1].bar_length as u16;
+
+        let (court, _) = court_layout(Rect::new(0, 0, MIN_TERMINAL_WIDTH, MIN_TERMINAL_HEIGHT));
+        game.set_area(court);
+        let (width, height) = Game::court_size(court);
+        let [x, y] = game.ball.position;
+        assert!((DEFAULT_PADDLE_WIDTH..width - DEFAULT_PADDLE_WIDTH).contains(&x));
+        assert!(y < height);
+        for player in &game.players {
+            assert!(player.bar_position + player.bar_length as u16 <= height);
+            assert!(player.bar_length >= MIN_BAR_LENGTH);
+        }
     }
 }
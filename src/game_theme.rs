@@ -1,5 +1,7 @@
 use ratatui::style::Color;
 
+use crate::color::{ColorSupport, Cvd};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GameTheme {
     Monokai,
@@ -9,8 +11,12 @@ pub enum GameTheme {
     Nord,
     OneDark,
     HighContrast,
+    // picked to stay distinguishable with color vision deficiencies
+    OkabeIto,
+    IbmAccessible,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct ThemeColors {
     pub background: Color,
     pub border: Color,
@@ -19,9 +25,148 @@ pub struct ThemeColors {
     pub player_bar: Color,
     pub player_bar_power: Color,
     pub ball: Color,
+    // menus and other screens
+    pub menu: Color,
+    pub selection: Color,
+    pub title: Color,
+    pub input: Color,
+}
+
+impl ThemeColors {
+    /// Labels for [`ThemeColors::slots`], in the order the settings preview
+    /// strip shows them.
+    pub const SLOT_NAMES: [&'static str; 11] = [
+        "Player Bar",
+        "Power Bar",
+        "Ball",
+        "Text",
+        "Accent",
+        "Border",
+        "Background",
+        "Menu",
+        "Selection",
+        "Title",
+        "Input",
+    ];
+
+    pub fn slots(&self) -> [Color; 11] {
+        [
+            self.player_bar,
+            self.player_bar_power,
+            self.ball,
+            self.text,
+            self.accent,
+            self.border,
+            self.background,
+            self.menu,
+            self.selection,
+            self.title,
+            self.input,
+        ]
+    }
+
+    fn map(&self, f: impl Fn(Color) -> Color) -> ThemeColors {
+        let mut colors = *self;
+        for i in 0..Self::SLOT_NAMES.len() {
+            let slot = colors.slot_mut(i);
+            *slot = f(*slot);
+        }
+        colors
+    }
+
+    /// The same colors as the terminal will actually show them.
+    pub fn for_support(&self, support: ColorSupport) -> ThemeColors {
+        self.map(|color| support.map(color))
+    }
+
+    /// The colors as seen with a color vision deficiency.
+    pub fn simulated(&self, cvd: Cvd) -> ThemeColors {
+        self.map(|color| cvd.simulate(color))
+    }
+
+    pub fn slot_mut(&mut self, index: usize) -> &mut Color {
+        match index {
+            0 => &mut self.player_bar,
+            1 => &mut self.player_bar_power,
+            2 => &mut self.ball,
+            3 => &mut self.text,
+            4 => &mut self.accent,
+            5 => &mut self.border,
+            6 => &mut self.background,
+            7 => &mut self.menu,
+            8 => &mut self.selection,
+            9 => &mut self.title,
+            _ => &mut self.input,
+        }
+    }
+}
+
+/// A named set of colors, either one of the built-in [`GameTheme`]s or a
+/// theme loaded from the user's themes directory.
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub name: String,
+    pub colors: ThemeColors,
+}
+
+impl From<GameTheme> for Theme {
+    fn from(theme: GameTheme) -> Self {
+        Self {
+            name: theme.name().to_string(),
+            colors: theme.colors(),
+        }
+    }
+}
+
+impl Theme {
+    pub fn builtin() -> Vec<Theme> {
+        GameTheme::ALL.into_iter().map(Theme::from).collect()
+    }
+}
+
+/// Finds a theme by name, ignoring case, spaces, dashes and underscores (so
+/// "Gruvbox Dark" and "gruvbox-dark" both match).
+pub fn find_theme(themes: &[Theme], name: &str) -> Option<usize> {
+    let wanted = normalize_theme_name(name);
+    themes
+        .iter()
+        .position(|theme| normalize_theme_name(&theme.name) == wanted)
+}
+
+fn normalize_theme_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '_'))
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
 }
 
 impl GameTheme {
+    pub const ALL: [GameTheme; 9] = [
+        GameTheme::Monokai,
+        GameTheme::Solarized,
+        GameTheme::Dracula,
+        GameTheme::GruvboxDark,
+        GameTheme::Nord,
+        GameTheme::OneDark,
+        GameTheme::HighContrast,
+        GameTheme::OkabeIto,
+        GameTheme::IbmAccessible,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameTheme::Monokai => "Monokai",
+            GameTheme::Solarized => "Solarized",
+            GameTheme::Dracula => "Dracula",
+            GameTheme::GruvboxDark => "Gruvbox Dark",
+            GameTheme::Nord => "Nord",
+            GameTheme::OneDark => "One Dark",
+            GameTheme::HighContrast => "High Contrast",
+            GameTheme::OkabeIto => "Okabe-Ito",
+            GameTheme::IbmAccessible => "IBM Accessible",
+        }
+    }
+
     pub fn colors(&self) -> ThemeColors {
         match self {
             GameTheme::Monokai => ThemeColors {
@@ -32,6 +177,10 @@ impl GameTheme {
                 player_bar: Color::Rgb(102, 217, 239), // Monokai cyan
                 player_bar_power: Color::Rgb(230, 219, 116), // Monokai yellow
                 ball: Color::Rgb(255, 95, 135),   // Monokai light pink
+                menu: Color::Rgb(166, 226, 46),   // Monokai green
+                selection: Color::Rgb(253, 151, 31), // Monokai orange
+                title: Color::Rgb(102, 217, 239), // Monokai cyan
+                input: Color::Rgb(166, 226, 46),  // Monokai green
             },
             GameTheme::Solarized => ThemeColors {
                 background: Color::Reset,
@@ -41,6 +190,10 @@ impl GameTheme {
                 player_bar: Color::Rgb(133, 153, 0), // Solarized green
                 player_bar_power: Color::Rgb(181, 137, 0), // Solarized yellow
                 ball: Color::Rgb(220, 50, 47),    // Solarized red
+                menu: Color::Rgb(42, 161, 152),   // Solarized cyan
+                selection: Color::Rgb(203, 75, 22), // Solarized orange
+                title: Color::Rgb(38, 139, 210),  // Solarized blue
+                input: Color::Rgb(133, 153, 0),   // Solarized green
             },
             GameTheme::Dracula => ThemeColors {
                 background: Color::Reset,
@@ -50,6 +203,10 @@ impl GameTheme {
                 player_bar: Color::Rgb(80, 250, 123), // Dracula green
                 player_bar_power: Color::Rgb(241, 250, 140), // Dracula yellow
                 ball: Color::Rgb(255, 85, 85),     // Dracula red
+                menu: Color::Rgb(139, 233, 253),   // Dracula cyan
+                selection: Color::Rgb(255, 184, 108), // Dracula orange
+                title: Color::Rgb(189, 147, 249),  // Dracula purple
+                input: Color::Rgb(80, 250, 123),   // Dracula green
             },
             GameTheme::GruvboxDark => ThemeColors {
                 background: Color::Reset,
@@ -59,6 +216,10 @@ impl GameTheme {
                 player_bar: Color::Rgb(131, 165, 152), // Gruvbox blue
                 player_bar_power: Color::Rgb(254, 128, 25), // Gruvbox orange
                 ball: Color::Rgb(251, 73, 52),    // Gruvbox red
+                menu: Color::Rgb(142, 192, 124),  // Gruvbox aqua
+                selection: Color::Rgb(250, 189, 47), // Gruvbox yellow
+                title: Color::Rgb(131, 165, 152), // Gruvbox blue
+                input: Color::Rgb(184, 187, 38),  // Gruvbox green
             },
             GameTheme::Nord => ThemeColors {
                 background: Color::Reset,
@@ -68,6 +229,10 @@ impl GameTheme {
                 player_bar: Color::Rgb(94, 129, 172), // Nord blue
                 player_bar_power: Color::Rgb(235, 203, 139), // Nord yellow
                 ball: Color::Rgb(191, 97, 106),       // Nord red
+                menu: Color::Rgb(143, 188, 187),      // Nord cyan
+                selection: Color::Rgb(235, 203, 139), // Nord yellow
+                title: Color::Rgb(136, 192, 208),     // Nord frost
+                input: Color::Rgb(163, 190, 140),     // Nord green
             },
             GameTheme::OneDark => ThemeColors {
                 background: Color::Reset,
@@ -77,6 +242,10 @@ impl GameTheme {
                 player_bar: Color::Rgb(152, 195, 121), // One Dark green
                 player_bar_power: Color::Rgb(229, 192, 123), // One Dark yellow
                 ball: Color::Rgb(224, 108, 117),   // One Dark red
+                menu: Color::Rgb(86, 182, 194),    // One Dark cyan
+                selection: Color::Rgb(229, 192, 123), // One Dark yellow
+                title: Color::Rgb(97, 175, 239),   // One Dark blue
+                input: Color::Rgb(152, 195, 121),  // One Dark green
             },
             GameTheme::HighContrast => ThemeColors {
                 background: Color::Black,                // true black for max contrast
@@ -86,7 +255,38 @@ impl GameTheme {
                 player_bar: Color::Rgb(0, 255, 255),     // bright cyan (player bar)
                 player_bar_power: Color::Rgb(0, 255, 0), // bright green (power bar)
                 ball: Color::Rgb(255, 0, 0),             // bright red (ball)
-                                                         // Extra accent colors can be added in the future if needed
+                menu: Color::White,                      // white menu items
+                selection: Color::Yellow,                // yellow highlight
+                title: Color::LightCyan,                 // bright cyan titles
+                input: Color::LightGreen,                // bright green input
+            },
+            // blue paddles against an orange ball is the pairing that survives
+            // protanopia, deuteranopia and tritanopia alike
+            GameTheme::OkabeIto => ThemeColors {
+                background: Color::Reset,
+                border: Color::Rgb(0, 114, 178), // Okabe-Ito blue
+                text: Color::Rgb(240, 240, 240), // near white
+                accent: Color::Rgb(0, 158, 115), // Okabe-Ito bluish green
+                player_bar: Color::Rgb(86, 180, 233), // Okabe-Ito sky blue
+                player_bar_power: Color::Rgb(240, 228, 66), // Okabe-Ito yellow
+                ball: Color::Rgb(230, 159, 0),   // Okabe-Ito orange
+                menu: Color::Rgb(86, 180, 233),  // Okabe-Ito sky blue
+                selection: Color::Rgb(240, 228, 66), // Okabe-Ito yellow
+                title: Color::Rgb(0, 114, 178),  // Okabe-Ito blue
+                input: Color::Rgb(0, 158, 115),  // Okabe-Ito bluish green
+            },
+            GameTheme::IbmAccessible => ThemeColors {
+                background: Color::Reset,
+                border: Color::Rgb(120, 94, 240),      // IBM purple
+                text: Color::Rgb(230, 230, 230),       // near white
+                accent: Color::Rgb(255, 176, 0),       // IBM gold
+                player_bar: Color::Rgb(100, 143, 255), // IBM blue
+                player_bar_power: Color::Rgb(255, 255, 255), // white, stands out by brightness
+                ball: Color::Rgb(254, 97, 0),          // IBM orange
+                menu: Color::Rgb(100, 143, 255),       // IBM blue
+                selection: Color::Rgb(255, 176, 0),    // IBM gold
+                title: Color::Rgb(120, 94, 240),       // IBM purple
+                input: Color::Rgb(100, 143, 255),      // IBM blue
             },
         }
     }
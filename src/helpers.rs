@@ -1,6 +1,9 @@
+use crossterm::event::{KeyEvent, KeyEventKind};
 use ratatui::layout::Rect;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
-use crate::game::PLAYER_NAME_CHAR_LEN;
+use crate::game::{PLAYER_NAME_MAX_GRAPHEMES, PLAYER_NAME_MAX_WIDTH};
 
 pub fn centered_rect_with_percentage(percent_x: u16, percent_y: u16, cols: u16, rows: u16) -> Rect {
     let width = cols * percent_x / 100;
@@ -12,7 +15,7 @@ pub fn centered_rect(width: u16, height: u16, cols: u16, rows: u16) -> Rect {
     // Ensure we don't try to create a rect larger than available space
     let actual_width = std::cmp::min(width, cols);
     let actual_height = std::cmp::min(height, rows);
-    
+
     // Safely calculate center position, avoiding underflow
     let x = if cols >= actual_width {
         (cols - actual_width) / 2
@@ -24,14 +27,77 @@ pub fn centered_rect(width: u16, height: u16, cols: u16, rows: u16) -> Rect {
     } else {
         0
     };
-    
+
     Rect::new(x, y, actual_width, actual_height)
 }
 
-pub fn string_to_char_array(s: &str) -> [char; PLAYER_NAME_CHAR_LEN] {
-    let mut chars = s.chars().collect::<Vec<char>>(); // Collect the string into a vector of chars
-    chars.resize(PLAYER_NAME_CHAR_LEN, ' '); // Pad with spaces if shorter than 16
-    let mut array = [' '; PLAYER_NAME_CHAR_LEN]; // Initialize an empty array
-    array.copy_from_slice(&chars[0..PLAYER_NAME_CHAR_LEN]); // Copy the first 16 characters
-    array
+/// Checks a player name: printable, and within the character and column
+/// limits. Wide characters like CJK and most emoji take two columns.
+pub fn validate_name(name: &str) -> Result<(), String> {
+    if name.chars().any(char::is_control) {
+        return Err("must not contain control characters".to_string());
+    }
+    if name.graphemes(true).count() > PLAYER_NAME_MAX_GRAPHEMES {
+        return Err(format!(
+            "is longer than {} characters",
+            PLAYER_NAME_MAX_GRAPHEMES
+        ));
+    }
+    if name.width() > PLAYER_NAME_MAX_WIDTH {
+        return Err(format!("is wider than {} columns", PLAYER_NAME_MAX_WIDTH));
+    }
+    Ok(())
+}
+
+/// Cuts `text` down to `max_width` columns, ending in "…" when shortened.
+/// Wide characters are never split.
+pub fn truncate_to_width(text: &str, max_width: usize) -> String {
+    if text.width() <= max_width {
+        return text.to_string();
+    }
+    let mut truncated = String::new();
+    let mut width = 0;
+    for grapheme in text.graphemes(true) {
+        width += grapheme.width();
+        if width + 1 > max_width {
+            break;
+        }
+        truncated.push_str(grapheme);
+    }
+    if max_width > 0 {
+        truncated.push('…');
+    }
+    truncated
+}
+
+/// A key press, or a held key repeating on terminals that report event types.
+/// Menus and text fields take both so holding a key keeps moving.
+pub fn is_key_press(key_event: &KeyEvent) -> bool {
+    matches!(key_event.kind, KeyEventKind::Press | KeyEventKind::Repeat)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_are_limited_by_characters_and_columns() {
+        assert!(validate_name("Zoë").is_ok());
+        // 16 combined characters, each one grapheme
+        assert!(validate_name(&"e\u{301}".repeat(16)).is_ok());
+        assert!(validate_name(&"a".repeat(17)).is_err());
+        // 12 wide characters fill 24 columns, 13 don't fit
+        assert!(validate_name(&"東".repeat(12)).is_ok());
+        assert!(validate_name(&"東".repeat(13)).is_err());
+        assert!(validate_name("tab\there").is_err());
+    }
+
+    #[test]
+    fn truncation_keeps_wide_characters_whole() {
+        assert_eq!(truncate_to_width("Alice", 10), "Alice");
+        assert_eq!(truncate_to_width("Alexandra", 6), "Alexa…");
+        assert_eq!(truncate_to_width("東京都庁", 6), "東京…");
+        assert_eq!(truncate_to_width("東京都庁", 5), "東京…");
+        assert_eq!(truncate_to_width("Bob", 0), "");
+    }
 }
@@ -0,0 +1,118 @@
+use ratatui::{
+    layout::Rect,
+    style::Color,
+    symbols::Marker,
+    widgets::canvas::{Canvas, Points},
+    Frame,
+};
+use serde::{Deserialize, Serialize};
+
+/// How the paddles and ball are drawn. `Classic` uses whole cells; the other
+/// two paint on a sub-cell canvas for finer shapes and smoother motion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RenderMode {
+    #[default]
+    Classic,
+    HalfBlock, // 1x2 pixels per cell
+    Braille,   // 2x4 dots per cell
+}
+
+impl RenderMode {
+    pub const ALL: [RenderMode; 3] = [
+        RenderMode::Classic,
+        RenderMode::HalfBlock,
+        RenderMode::Braille,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RenderMode::Classic => "Classic",
+            RenderMode::HalfBlock => "Half-block",
+            RenderMode::Braille => "Braille",
+        }
+    }
+
+    // canvas pixels per terminal cell, horizontally and vertically
+    fn scale(&self) -> (f64, f64) {
+        match self {
+            RenderMode::Classic => (1.0, 1.0),
+            RenderMode::HalfBlock => (1.0, 2.0),
+            RenderMode::Braille => (2.0, 4.0),
+        }
+    }
+
+    fn marker(&self) -> Marker {
+        match self {
+            RenderMode::Classic => Marker::Block,
+            RenderMode::HalfBlock => Marker::HalfBlock,
+            RenderMode::Braille => Marker::Braille,
+        }
+    }
+}
+
+/// A filled rectangle on the court, in cell units relative to its top-left
+/// corner. Fractional values land on the nearest canvas pixel.
+pub struct Shape {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub color: Color,
+    pub round: bool, // trims the corner pixels, for the ball
+}
+
+/// Paints `shapes` over `area`, the inside of the court's border.
+pub fn draw_court(
+    frame: &mut Frame,
+    area: Rect,
+    mode: RenderMode,
+    background: Color,
+    shapes: &[Shape],
+) {
+    let (scale_x, scale_y) = mode.scale();
+    let width = area.width as f64 * scale_x;
+    let height = area.height as f64 * scale_y;
+
+    // one canvas unit per pixel, with y flipped so shapes use screen rows
+    let pixels = shapes
+        .iter()
+        .map(|shape| {
+            let left = (shape.x * scale_x).round() as i32;
+            let top = (shape.y * scale_y).round() as i32;
+            let right = ((shape.x + shape.width) * scale_x).round() as i32;
+            let bottom = ((shape.y + shape.height) * scale_y).round() as i32;
+            let mut coords = Vec::new();
+            for py in top..bottom.max(top + 1) {
+                for px in left..right.max(left + 1) {
+                    let is_corner = (px == left || px == right - 1)
+                        && (py == top || py == bottom - 1)
+                        && right - left > 2
+                        && bottom - top > 2;
+                    if shape.round && is_corner {
+                        continue;
+                    }
+                    if px >= 0 && py >= 0 && (px as f64) < width && (py as f64) < height {
+                        coords.push((px as f64, height - 1.0 - py as f64));
+                    }
+                }
+            }
+            (coords, shape.color)
+        })
+        .collect::<Vec<_>>();
+
+    let canvas = Canvas::default()
+        .marker(mode.marker())
+        .background_color(background)
+        .x_bounds([0.0, (width - 1.0).max(1.0)])
+        .y_bounds([0.0, (height - 1.0).max(1.0)])
+        .paint(|ctx| {
+            for (coords, color) in pixels.iter() {
+                ctx.draw(&Points {
+                    coords,
+                    color: *color,
+                });
+            }
+        });
+    frame.render_widget(canvas, area);
+}
@@ -0,0 +1,352 @@
+use crossterm::event::KeyCode;
+use serde::{Deserialize, Serialize};
+
+/// Everything a key can be bound to during a match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    P1Up,
+    P1Down,
+    P1Power,
+    P2Up,
+    P2Down,
+    P2Power,
+    Pause,
+    Quit,
+    Debug,
+}
+
+impl Action {
+    pub const ALL: [Action; 9] = [
+        Action::P1Up,
+        Action::P1Down,
+        Action::P1Power,
+        Action::P2Up,
+        Action::P2Down,
+        Action::P2Power,
+        Action::Pause,
+        Action::Quit,
+        Action::Debug,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::P1Up => "Player 1 Up",
+            Action::P1Down => "Player 1 Down",
+            Action::P1Power => "Player 1 Power",
+            Action::P2Up => "Player 2 Up",
+            Action::P2Down => "Player 2 Down",
+            Action::P2Power => "Player 2 Power",
+            Action::Pause => "Pause",
+            Action::Quit => "Quit",
+            Action::Debug => "Debug Overlay",
+        }
+    }
+
+    /// The paddle this action moves, and which way (1 is up).
+    pub fn movement(&self) -> Option<(usize, i8)> {
+        match self {
+            Action::P1Up => Some((0, 1)),
+            Action::P1Down => Some((0, -1)),
+            Action::P2Up => Some((1, 1)),
+            Action::P2Down => Some((1, -1)),
+            _ => None,
+        }
+    }
+}
+
+/// A key as written in the config file: `"up"`, `"space"`, `"f3"`, `"w"`...
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Key(pub KeyCode);
+
+impl TryFrom<String> for Key {
+    type Error = String;
+
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        let lower = name.to_ascii_lowercase();
+        let code = match lower.as_str() {
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "space" => KeyCode::Char(' '),
+            "enter" => KeyCode::Enter,
+            "esc" => KeyCode::Esc,
+            "tab" => KeyCode::Tab,
+            "backspace" => KeyCode::Backspace,
+            "delete" => KeyCode::Delete,
+            "insert" => KeyCode::Insert,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            _ => {
+                let mut chars = lower.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) if !c.is_control() => KeyCode::Char(c),
+                    _ => match lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                        Some(n) if (1..=12).contains(&n) => KeyCode::F(n),
+                        _ => return Err(format!("unknown key \"{}\"", name)),
+                    },
+                }
+            }
+        };
+        Ok(Key(code))
+    }
+}
+
+impl From<Key> for String {
+    fn from(key: Key) -> Self {
+        match key.0 {
+            KeyCode::Up => "up".to_string(),
+            KeyCode::Down => "down".to_string(),
+            KeyCode::Left => "left".to_string(),
+            KeyCode::Right => "right".to_string(),
+            KeyCode::Char(' ') => "space".to_string(),
+            KeyCode::Enter => "enter".to_string(),
+            KeyCode::Esc => "esc".to_string(),
+            KeyCode::Tab => "tab".to_string(),
+            KeyCode::Backspace => "backspace".to_string(),
+            KeyCode::Delete => "delete".to_string(),
+            KeyCode::Insert => "insert".to_string(),
+            KeyCode::Home => "home".to_string(),
+            KeyCode::End => "end".to_string(),
+            KeyCode::PageUp => "pageup".to_string(),
+            KeyCode::PageDown => "pagedown".to_string(),
+            KeyCode::F(n) => format!("f{}", n),
+            KeyCode::Char(c) => c.to_string(),
+            other => format!("{:?}", other).to_ascii_lowercase(),
+        }
+    }
+}
+
+impl Key {
+    /// Whether the key can be stored and read back from the config file.
+    pub fn is_bindable(code: KeyCode) -> bool {
+        let name = String::from(Key(code));
+        Key::try_from(name).is_ok_and(|key| key.0 == code)
+    }
+
+    /// Short name for the controls bar and menus.
+    pub fn label(&self) -> String {
+        match self.0 {
+            KeyCode::Up => "↑".to_string(),
+            KeyCode::Down => "↓".to_string(),
+            KeyCode::Left => "←".to_string(),
+            KeyCode::Right => "→".to_string(),
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(c) if c.is_ascii_punctuation() => format!("'{}'", c),
+            KeyCode::Char(c) => c.to_uppercase().to_string(),
+            KeyCode::F(n) => format!("F{}", n),
+            _ => {
+                let name = String::from(*self);
+                let mut chars = name.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(chars).collect())
+                    .unwrap_or_default()
+            }
+        }
+    }
+}
+
+/// Up to two keys per action, saved under `[key_bindings]` in the config.
+/// Letters are matched case-insensitively.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub p1_up: Vec<Key>,
+    pub p1_down: Vec<Key>,
+    pub p1_power: Vec<Key>,
+    pub p2_up: Vec<Key>,
+    pub p2_down: Vec<Key>,
+    pub p2_power: Vec<Key>,
+    pub pause: Vec<Key>,
+    pub quit: Vec<Key>,
+    pub debug: Vec<Key>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        let keys = |codes: &[KeyCode]| codes.iter().map(|code| Key(*code)).collect();
+        Self {
+            p1_up: keys(&[KeyCode::Up]),
+            p1_down: keys(&[KeyCode::Down]),
+            p1_power: keys(&[KeyCode::Char('/')]),
+            p2_up: keys(&[KeyCode::Char('w')]),
+            p2_down: keys(&[KeyCode::Char('s')]),
+            p2_power: keys(&[KeyCode::Char(' ')]),
+            pause: keys(&[KeyCode::Char('p')]),
+            quit: keys(&[KeyCode::Esc, KeyCode::Char('q')]),
+            debug: keys(&[KeyCode::F(3)]),
+        }
+    }
+}
+
+pub const KEYS_PER_ACTION: usize = 2;
+
+impl KeyBindings {
+    pub fn keys(&self, action: Action) -> &Vec<Key> {
+        match action {
+            Action::P1Up => &self.p1_up,
+            Action::P1Down => &self.p1_down,
+            Action::P1Power => &self.p1_power,
+            Action::P2Up => &self.p2_up,
+            Action::P2Down => &self.p2_down,
+            Action::P2Power => &self.p2_power,
+            Action::Pause => &self.pause,
+            Action::Quit => &self.quit,
+            Action::Debug => &self.debug,
+        }
+    }
+
+    pub fn keys_mut(&mut self, action: Action) -> &mut Vec<Key> {
+        match action {
+            Action::P1Up => &mut self.p1_up,
+            Action::P1Down => &mut self.p1_down,
+            Action::P1Power => &mut self.p1_power,
+            Action::P2Up => &mut self.p2_up,
+            Action::P2Down => &mut self.p2_down,
+            Action::P2Power => &mut self.p2_power,
+            Action::Pause => &mut self.pause,
+            Action::Quit => &mut self.quit,
+            Action::Debug => &mut self.debug,
+        }
+    }
+
+    pub fn action_for(&self, code: KeyCode) -> Option<Action> {
+        let code = normalize(code);
+        Action::ALL
+            .into_iter()
+            .find(|action| self.keys(*action).iter().any(|key| key.0 == code))
+    }
+
+    pub fn is_bound(&self, action: Action, code: KeyCode) -> bool {
+        let code = normalize(code);
+        self.keys(action).iter().any(|key| key.0 == code)
+    }
+
+    /// The first key of `action`, for short hints like "P=Pause".
+    pub fn label(&self, action: Action) -> String {
+        self.keys(action)
+            .first()
+            .map(Key::label)
+            .unwrap_or_else(|| "-".to_string())
+    }
+
+    /// Checks that every action has a key and no key does two things.
+    pub fn validate(&self) -> Result<(), String> {
+        for (i, action) in Action::ALL.iter().enumerate() {
+            let keys = self.keys(*action);
+            if keys.is_empty() {
+                return Err(format!("{} has no key", action.name()));
+            }
+            if keys.len() > KEYS_PER_ACTION {
+                return Err(format!(
+                    "{} has more than {} keys",
+                    action.name(),
+                    KEYS_PER_ACTION
+                ));
+            }
+            for key in keys {
+                if let Some(other) = Action::ALL[i + 1..]
+                    .iter()
+                    .find(|other| self.keys(**other).contains(key))
+                {
+                    return Err(format!(
+                        "{} is bound to both {} and {}",
+                        key.label(),
+                        action.name(),
+                        other.name()
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// shifted letters still trigger their binding
+fn normalize(code: KeyCode) -> KeyCode {
+    match code {
+        KeyCode::Char(c) => KeyCode::Char(c.to_ascii_lowercase()),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_names_round_trip() {
+        let codes = [
+            KeyCode::Up,
+            KeyCode::PageDown,
+            KeyCode::Char(' '),
+            KeyCode::Char('w'),
+            KeyCode::Char('/'),
+            KeyCode::F(3),
+            KeyCode::F(12),
+            KeyCode::Esc,
+        ];
+        for code in codes {
+            let name = String::from(Key(code));
+            assert_eq!(Key::try_from(name).unwrap(), Key(code));
+            assert!(Key::is_bindable(code));
+        }
+        assert_eq!(
+            Key::try_from("PageUp".to_string()),
+            Ok(Key(KeyCode::PageUp))
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        assert!(Key::try_from("f13".to_string()).is_err());
+        assert!(Key::try_from("ctrl".to_string()).is_err());
+        assert!(Key::try_from(String::new()).is_err());
+        assert!(!Key::is_bindable(KeyCode::CapsLock));
+    }
+
+    #[test]
+    fn bindings_round_trip_through_toml() {
+        let bindings = KeyBindings {
+            p2_power: vec![Key(KeyCode::Char('e')), Key(KeyCode::Tab)],
+            ..KeyBindings::default()
+        };
+        let text = toml::to_string(&bindings).unwrap();
+        assert_eq!(toml::from_str::<KeyBindings>(&text).unwrap(), bindings);
+    }
+
+    #[test]
+    fn missing_actions_keep_their_defaults() {
+        let bindings = toml::from_str::<KeyBindings>("pause = [\"f1\"]").unwrap();
+        assert_eq!(bindings.pause, vec![Key(KeyCode::F(1))]);
+        assert_eq!(bindings.quit, KeyBindings::default().quit);
+    }
+
+    #[test]
+    fn validate_catches_conflicts() {
+        assert!(KeyBindings::default().validate().is_ok());
+
+        let mut bindings = KeyBindings::default();
+        bindings.p2_up.push(Key(KeyCode::Up));
+        assert!(bindings.validate().is_err());
+
+        let mut bindings = KeyBindings::default();
+        bindings.pause.clear();
+        assert!(bindings.validate().is_err());
+
+        let mut bindings = KeyBindings::default();
+        bindings.quit.push(Key(KeyCode::F(1)));
+        assert!(bindings.validate().is_err());
+    }
+
+    #[test]
+    fn shifted_letters_match_their_binding() {
+        let bindings = KeyBindings::default();
+        assert_eq!(bindings.action_for(KeyCode::Char('W')), Some(Action::P2Up));
+        assert!(bindings.is_bound(Action::Quit, KeyCode::Char('Q')));
+    }
+}
@@ -1,158 +1,660 @@
 use std::{
+    env,
     io::{self},
+    path::PathBuf,
     thread::sleep,
     time::Duration,
 };
 
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEventKind},
-    ExecutableCommand,
+    event::{
+        self, Event, KeyCode, KeyEvent, KeyboardEnhancementFlags, MouseButton, MouseEvent,
+        MouseEventKind, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    },
+    terminal, ExecutableCommand,
 };
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Flex, Layout, Margin, Rect},
+    layout::{Alignment, Constraint, Direction, Flex, Layout, Margin, Position, Rect},
     style::{Color, Style, Stylize},
-    widgets::{Block, BorderType, Borders, Paragraph},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph, Row, Table, Wrap},
     DefaultTerminal, Frame,
 };
 use tui_big_text::{BigText, PixelSize};
 
+mod bindings_editor;
+mod cli;
+mod color;
+mod config;
+mod effects;
 mod game;
 mod game_theme;
 mod helpers;
+mod hires;
+mod keybindings;
+mod menu;
+mod profile;
+mod profile_editor;
+mod rating;
+mod theme_editor;
+mod theme_file;
 use crate::{
-    game::{Game, GameType, PLAYER_NAME_CHAR_LEN},
-    helpers::{centered_rect, centered_rect_with_percentage},
+    cli::CliCommand,
+    config::Config,
+    game::{Game, GameType},
+    helpers::{centered_rect, centered_rect_with_percentage, is_key_press},
 };
 
-#[derive(Debug)]
-struct MainMenu {
-    options: Vec<&'static str>,
-    selected: usize,
+#[derive(Debug, Clone, Copy)]
+enum MainAction {
+    VsAi,
+    WithFriend,
+    Watch,
+    Leaderboard,
+    Settings,
+    Exit,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum PickerField {
+    Player(usize), // which profile plays in this slot
+    MatchLength,
+    Start,
+    NewProfile,
+    EditProfile(usize),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Setting {
+    DifficultyVsAi,
+    DifficultyWithFriend,
+    DifficultyScreensaver,
+    Theme,
+    EditTheme,
+    Renderer,
+    ReducedMotion,
+    MouseControl,
+    KeyBindings,
 }
 
 #[derive(Debug)]
 enum AppScreen {
     MainMenu,
-    PlayerNameInput { current: usize, max: usize },
+    ProfilePicker(GameType),
     Game,
+    Leaderboard,
     Settings,
 }
 
-use crate::game_theme::GameTheme;
+use crate::{
+    bindings_editor::{BindingsAction, BindingsEditor},
+    color::{ColorSupport, Cvd},
+    game_theme::{find_theme, Theme, ThemeColors},
+    hires::RenderMode,
+    keybindings::{Action, KeyBindings},
+    menu::{ItemKind, Menu, MenuEvent, MenuItem, MenuStyle},
+    profile::{MatchRecord, Profile},
+    profile_editor::{ProfileAction, ProfileEditor},
+    rating::RatedMatch,
+    theme_editor::{EditorAction, ThemeEditor},
+};
 
 struct App {
     exit: bool,
-    main_menu: MainMenu,
+    main_menu: Menu<MainAction>,
     current_game: Option<Game>,
     screen: AppScreen,
-    name_input: String,
-    player_names: [String; 2],
+    picker_menu: Menu<PickerField>,
+    picked_profiles: [Option<usize>; 2], // None plays as a guest
+    picker_message: Option<String>,
+    profiles: Vec<Profile>,
+    profiles_path: Option<PathBuf>,
+    profiles_error: Option<String>,
+    profile_editor: Option<ProfileEditor>,
+    // who's playing the current match, and how it started
+    match_profiles: [Option<usize>; 2],
+    match_bindings: KeyBindings,
+    match_own_keys: [bool; 2], // the profile in that slot plays with its own keys
+    match_difficulty: f32,
+    leaderboard_message: Option<String>,
     // Settings
     default_difficulty_vs_ai: f32,
     default_difficulty_with_friend: f32,
     default_difficulty_screensaver: f32,
-    selected_theme: GameTheme,
-    settings_selected: usize, // 0: vs AI, 1: with friend, 2: screensaver, 3: theme, 4: back
+    themes: Vec<Theme>, // built-ins followed by custom theme files
+    selected_theme: usize,
+    render_mode: RenderMode,
+    reduced_motion: bool, // no trails, particles or shake
+    max_fps: u32,         // render cap during a match, from the config file only
+    mouse_control: bool,  // player 1 follows the pointer instead of the wheel
+    key_bindings: KeyBindings,
+    settings_menu: Menu<Setting>,
+    swatch_areas: Vec<Rect>, // last drawn color swatches, for the mouse
+    config_path: Option<PathBuf>,
+    config_writable: bool, // false when the config file couldn't be read
+    config_error: Option<String>,
+    theme_errors: Vec<String>,
+    theme_editor: Option<ThemeEditor>,
+    bindings_editor: Option<BindingsEditor>,
+    color_support: ColorSupport,
+    color_support_forced: bool, // set with --colors instead of detected
+    cvd_preview: Cvd,           // vision type simulated by the settings preview
+    key_release_events: bool,   // the terminal reports key releases
+    // from the command line, applied to every match
+    seed: Option<u64>,
+    first_to: Option<u32>,
+    // picked under Match, for the matches started from the picker
+    picked_first_to: Option<Option<u32>>,
 }
 
-const MAIN_MENU_OPTIONS: [&str; 5] = [
-    "Play vs. AI",
-    "Play with Friend",
-    "I like to watch",
-    "Settings",
-    "Exit",
-];
+const COMPUTER_NAME: &str = "Computer";
+// the picker's match lengths; only matches played to the end are rated
+const MATCH_LENGTHS: [Option<u32>; 6] = [None, Some(3), Some(5), Some(7), Some(11), Some(21)];
+// the length a match with a profile in it gets unless another is picked
+const RATED_FIRST_TO: u32 = 5;
+const SCREENSAVER_NAMES: [&str; 2] = ["Forg", "Car"];
+
+const PREVIEW_SLOTS_PER_ROW: usize = 6;
+const MENU_IDLE_TIMEOUT: Duration = Duration::from_secs(1);
+
+fn main_menu_items() -> Vec<MenuItem<MainAction>> {
+    vec![
+        MenuItem::new("Play vs. AI", ItemKind::Action(MainAction::VsAi)),
+        MenuItem::new("Play with Friend", ItemKind::Action(MainAction::WithFriend)),
+        MenuItem::new("I like to watch", ItemKind::Action(MainAction::Watch)),
+        MenuItem::new("Leaderboard", ItemKind::Action(MainAction::Leaderboard)),
+        MenuItem::new("Settings", ItemKind::Action(MainAction::Settings)),
+        MenuItem::new("Exit", ItemKind::Action(MainAction::Exit)),
+    ]
+}
 
 impl App {
-    fn new() -> Self {
-        let main_menu = MainMenu {
-            options: MAIN_MENU_OPTIONS.to_vec(),
-            selected: 0,
+    fn new(config: Config, config_path: Option<PathBuf>, config_error: Option<String>) -> Self {
+        let mut themes = Theme::builtin();
+        let mut theme_errors = Vec::new();
+        if let Some(path) = config_path.as_deref() {
+            let (custom, errors) =
+                theme_file::load_custom_themes(&Config::themes_dir(path), &themes);
+            themes.extend(custom);
+            theme_errors = errors;
+        }
+
+        let mut config_error = config_error;
+        let selected_theme = find_theme(&themes, &config.theme).unwrap_or_else(|| {
+            config_error.get_or_insert(format!("Unknown theme \"{}\" in config", config.theme));
+            0
+        });
+        let key_bindings = match config.key_bindings.validate() {
+            Ok(()) => config.key_bindings,
+            Err(e) => {
+                config_error.get_or_insert(format!("Key bindings in config: {}", e));
+                KeyBindings::default()
+            }
         };
 
         Self {
             exit: false,
-            main_menu: main_menu,
+            main_menu: Menu::new(main_menu_items()),
             current_game: None,
             screen: AppScreen::MainMenu,
-            name_input: String::new(),
-            player_names: [String::new(), String::new()],
-            default_difficulty_vs_ai: 0.8,
-            default_difficulty_with_friend: 1.0,
-            default_difficulty_screensaver: 1.2,
-            selected_theme: GameTheme::Monokai,
-            settings_selected: 0,
+            picker_menu: Menu::new(Vec::new()),
+            picked_profiles: [None, None],
+            picker_message: None,
+            profiles: Vec::new(),
+            profiles_path: None,
+            profiles_error: None,
+            profile_editor: None,
+            match_profiles: [None, None],
+            match_bindings: KeyBindings::default(),
+            match_own_keys: [false; 2],
+            match_difficulty: 0.0,
+            leaderboard_message: None,
+            default_difficulty_vs_ai: config.default_difficulty_vs_ai.clamp(0.0, 2.0),
+            default_difficulty_with_friend: config.default_difficulty_with_friend.clamp(0.0, 2.0),
+            default_difficulty_screensaver: config.default_difficulty_screensaver.clamp(0.0, 2.0),
+            themes,
+            selected_theme,
+            render_mode: config.render_mode,
+            reduced_motion: config.reduced_motion,
+            max_fps: config.max_fps.clamp(10, 240),
+            mouse_control: config.mouse_control,
+            key_bindings,
+            settings_menu: Menu::new(Vec::new()),
+            swatch_areas: Vec::new(),
+            config_path,
+            config_writable: true,
+            config_error,
+            theme_errors,
+            theme_editor: None,
+            bindings_editor: None,
+            color_support: ColorSupport::detect(),
+            color_support_forced: false,
+            cvd_preview: Cvd::None,
+            key_release_events: false,
+            seed: None,
+            first_to: None,
+            picked_first_to: None,
+        }
+    }
+
+    fn start_game(
+        &mut self,
+        player_names: [&str; 2],
+        profiles: [Option<usize>; 2],
+        game_type: GameType,
+        difficulty: f32,
+    ) {
+        let mut game = Game::new(player_names, Rect::default(), game_type, Some(difficulty));
+        game.set_theme(self.themes.clone(), self.selected_theme);
+        game.set_color_support(self.color_support);
+        game.set_render_mode(self.render_mode);
+        game.set_reduced_motion(self.reduced_motion);
+        game.set_max_fps(self.max_fps);
+        game.set_mouse_control(self.mouse_control);
+        // each profile brings its own keys for the side it plays on
+        let mut bindings = self.key_bindings.clone();
+        let mut own_keys = [false; 2];
+        for (slot, index) in profiles.iter().enumerate() {
+            if let Some(profile) = index.map(|index| &self.profiles[index]) {
+                profile.apply_keys(slot, &mut bindings);
+                own_keys[slot] = profile.key_bindings.is_some();
+                game.set_paddle_color(slot, profile.paddle_color.color());
+            }
+        }
+        if bindings.validate().is_err() {
+            bindings = self.key_bindings.clone();
+            own_keys = [false; 2];
+        }
+        game.set_key_bindings(bindings.clone());
+        self.match_bindings = bindings;
+        self.match_own_keys = own_keys;
+        self.match_profiles = profiles;
+        self.match_difficulty = difficulty;
+        game.set_key_release_events(self.key_release_events);
+        if let Some(seed) = self.seed {
+            game.set_seed(seed);
+        }
+        // a screensaver isn't played for a result
+        game.set_first_to(if game_type == GameType::ScreenSaver {
+            self.first_to
+        } else {
+            self.match_first_to(profiles)
+        });
+        self.current_game = Some(game);
+        self.screen = AppScreen::Game;
+    }
+
+    // jump straight into a match requested on the command line
+    fn launch(
+        &mut self,
+        game_type: GameType,
+        player_names: [Option<String>; 2],
+        difficulty: Option<f32>,
+    ) {
+        let (default_names, default_difficulty) = match game_type {
+            GameType::AgainstAi => (["Player 1", COMPUTER_NAME], self.default_difficulty_vs_ai),
+            GameType::WithFriend => (
+                ["Player 1", "Player 2"],
+                self.default_difficulty_with_friend,
+            ),
+            GameType::ScreenSaver => (SCREENSAVER_NAMES, self.default_difficulty_screensaver),
+        };
+        let [p1, p2] = player_names;
+        let p1 = p1.unwrap_or_else(|| default_names[0].to_string());
+        let p2 = p2.unwrap_or_else(|| default_names[1].to_string());
+        // names of existing profiles play as those profiles
+        let mut profiles = [None, None];
+        if game_type != GameType::ScreenSaver {
+            profiles[0] = profile::find_profile(&self.profiles, &p1);
+        }
+        if game_type == GameType::WithFriend {
+            // one profile can't play both sides
+            profiles[1] = profile::find_profile(&self.profiles, &p2)
+                .filter(|index| profiles[0] != Some(*index));
+        }
+        let names = [0, 1].map(|i| match profiles[i] {
+            Some(index) => self.profiles[index].name.clone(),
+            None => [&p1, &p2][i].clone(),
+        });
+        self.start_game(
+            [names[0].as_str(), names[1].as_str()],
+            profiles,
+            game_type,
+            difficulty.unwrap_or(default_difficulty),
+        );
+    }
+
+    fn set_key_release_events(&mut self, enabled: bool) {
+        self.key_release_events = enabled;
+        if let Some(game) = self.current_game.as_mut() {
+            game.set_key_release_events(enabled);
+        }
+    }
+
+    fn colors(&self) -> ThemeColors {
+        self.themes[self.selected_theme]
+            .colors
+            .for_support(self.color_support)
+    }
+
+    // highlighted menu entries; reverse video stands in for color when there is none
+    fn selection_style(&self) -> Style {
+        let style = Style::default()
+            .bg(Color::Reset)
+            .fg(self.colors().selection)
+            .bold();
+        if self.color_support.is_monochrome() {
+            style.reversed()
+        } else {
+            style
+        }
+    }
+
+    fn select_theme(&mut self, name: &str) -> Result<(), String> {
+        self.selected_theme = find_theme(&self.themes, name).ok_or_else(|| {
+            let known = self
+                .themes
+                .iter()
+                .map(|theme| theme.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("unknown theme \"{}\" (available: {})", name, known)
+        })?;
+        Ok(())
+    }
+
+    fn config(&self) -> Config {
+        // repeated ±0.1 steps drift, keep the saved values readable
+        let round = |difficulty: f32| (difficulty * 100.0).round() / 100.0;
+        Config {
+            default_difficulty_vs_ai: round(self.default_difficulty_vs_ai),
+            default_difficulty_with_friend: round(self.default_difficulty_with_friend),
+            default_difficulty_screensaver: round(self.default_difficulty_screensaver),
+            theme: self.themes[self.selected_theme].name.clone(),
+            render_mode: self.render_mode,
+            reduced_motion: self.reduced_motion,
+            max_fps: self.max_fps,
+            mouse_control: self.mouse_control,
+            key_bindings: self.key_bindings.clone(),
+        }
+    }
+
+    fn load_profiles(&mut self, path: Option<PathBuf>) {
+        match path.as_deref().map(profile::load) {
+            Some(Ok(profiles)) => {
+                self.profiles = profiles;
+                self.profiles_path = path;
+            }
+            // keep the file as it is rather than overwrite it
+            Some(Err(e)) => self.profiles_error = Some(format!("{} (profiles won't be saved)", e)),
+            None => {}
+        }
+    }
+
+    fn save_profiles(&mut self) {
+        if let Some(path) = self.profiles_path.as_ref() {
+            self.profiles_error = profile::save(path, &self.profiles).err();
+        }
+    }
+
+    fn save_config(&mut self) {
+        if !self.config_writable {
+            return;
+        }
+        if let Some(path) = self.config_path.as_ref() {
+            self.config_error = self.config().save(path).err();
+        }
+    }
+
+    // keys rebound from the pause menu go back where they came from: a
+    // profile's own keys to the profile, the rest to the config. Returns
+    // whether a profile changed
+    fn keep_match_key_changes(&mut self, game: &Game) -> bool {
+        let mut bindings = self.key_bindings.clone();
+        let mut profile_changed = false;
+        for action in Action::ALL {
+            let keys = game.key_bindings().keys(action);
+            if keys == self.match_bindings.keys(action) {
+                continue;
+            }
+            let own = (0..2)
+                .filter(|slot| self.match_own_keys[*slot])
+                .find(|slot| profile::slot_actions(*slot).contains(&action))
+                .and_then(|slot| self.match_profiles[slot])
+                .and_then(|index| self.profiles[index].key_bindings.as_mut());
+            match own {
+                Some(own) => {
+                    *own.keys_mut(action) = keys.clone();
+                    profile_changed = true;
+                }
+                None => *bindings.keys_mut(action) = keys.clone(),
+            }
+        }
+        // the match checked its keys with the profiles' in place; without
+        // them the config's own set might clash
+        if bindings != self.key_bindings && bindings.validate().is_ok() {
+            self.key_bindings = bindings;
+            self.save_config();
+        }
+        profile_changed
+    }
+
+    // persist settings when leaving the settings screen
+    fn leave_settings(&mut self) {
+        self.save_config();
+        self.screen = AppScreen::MainMenu;
+    }
+
+    // keep what was changed from the pause menu once the match is over, and
+    // add the match to the players' stats
+    fn finish_game(&mut self, game: &Game) {
+        let profile_keys_changed = self.keep_match_key_changes(game);
+        let changed = game.theme_index() != self.selected_theme
+            || game.reduced_motion() != self.reduced_motion;
+        if changed {
+            self.selected_theme = game.theme_index();
+            self.reduced_motion = game.reduced_motion();
+            self.save_config();
+        }
+
+        if let Some(winner) = game.winning_slot() {
+            self.rate_match(game, winner);
+        }
+
+        // a match left before anyone scored doesn't count
+        let played = game.winning_slot().is_some()
+            || game.get_player(0).score + game.get_player(1).score > 0;
+        let mut recorded = false;
+        for (slot, index) in self.match_profiles.iter().enumerate() {
+            let Some(profile) = index.and_then(|index| self.profiles.get_mut(index)) else {
+                continue;
+            };
+            if played {
+                let player = game.player_for_slot(slot);
+                profile.stats.record(&MatchRecord {
+                    won: game.winning_slot() == Some(slot),
+                    points: player.score,
+                    longest_rally: game.longest_rally(),
+                    power_moves_used: player.power_moves_used() as u32,
+                });
+                recorded = true;
+            }
+        }
+        self.match_profiles = [None, None];
+        if recorded || profile_keys_changed {
+            self.save_profiles();
+        }
+
+        if game.quit_requested() {
+            self.exit = true;
+        }
+    }
+
+    // Elo for the profiles in a match that was played to the end. Guests
+    // aren't rated, so neither is a friendly match against one
+    fn rate_match(&mut self, game: &Game, winner: usize) {
+        let difficulty = game.difficulty();
+        let opponents = match (game.game_type(), self.match_profiles) {
+            // a difficulty changed mid-match can't be rated fairly
+            (GameType::AgainstAi, _) if difficulty != self.match_difficulty => return,
+            (GameType::AgainstAi, _) => [
+                Some((
+                    format!("{} ({:.1})", COMPUTER_NAME, difficulty),
+                    rating::ai_rating(difficulty),
+                )),
+                None,
+            ],
+            (GameType::WithFriend, [Some(p1), Some(p2)]) => [p2, p1].map(|opponent| {
+                let opponent = &self.profiles[opponent];
+                Some((opponent.name.clone(), opponent.rating))
+            }),
+            _ => return,
+        };
+
+        let timestamp = rating::now();
+        let points = [0, 1].map(|slot| game.player_for_slot(slot).score);
+        for (slot, opponent) in opponents.into_iter().enumerate() {
+            let (Some(index), Some((opponent, opponent_rating))) =
+                (self.match_profiles[slot], opponent)
+            else {
+                continue;
+            };
+            let profile = &mut self.profiles[index];
+            let won = winner == slot;
+            let rating_after = rating::updated_rating(profile.rating, opponent_rating, won);
+            profile.history.push(RatedMatch {
+                timestamp,
+                opponent,
+                won,
+                points: points[slot],
+                opponent_points: points[1 - slot],
+                rating_before: profile.rating,
+                rating_after,
+            });
+            profile.rating = rating_after;
         }
     }
 
     pub fn run(&mut self, mut terminal: DefaultTerminal) -> io::Result<()> {
         let mut last_size: u8 = 0; // 0 -> too small | 1 -> normal
+        let mut needs_redraw = true;
+        let mut drawn_size = terminal.size()?;
 
         while !self.exit {
-            let min_width = 130;
-            let min_height = 28;
+            let min_width = game::MIN_TERMINAL_WIDTH;
+            let min_height = game::MIN_TERMINAL_HEIGHT;
 
             let size = terminal.size()?;
+            if size != drawn_size {
+                drawn_size = size;
+                needs_redraw = true;
+            }
             if size.width < min_width || size.height < min_height {
+                if let Some(game) = self.current_game.as_mut() {
+                    game.auto_pause();
+                }
                 if last_size == 1 {
                     sleep(Duration::from_millis(100));
                     last_size = 0;
+                    needs_redraw = true;
                 }
-                self.handle_events()?;
-                terminal.draw(|frame| self.show_terminal_resize_warning(frame))?;
-            } else {
-                if last_size == 0 {
-                    sleep(Duration::from_millis(100));
-                    let game_area = centered_rect(130, 28, size.width, size.height);
-                    match self.current_game.as_mut() {
-                        Some(game) => game.set_area(game_area),
-                        None => {}
+                if needs_redraw {
+                    terminal.draw(|frame| self.show_terminal_resize_warning(frame))?;
+                    needs_redraw = false;
+                }
+                if let Some(event) = Self::wait_for_event()? {
+                    // a paused match and the other screens wait for the size
+                    // to come back rather than take input they aren't showing
+                    if matches!(self.screen, AppScreen::MainMenu) {
+                        self.handle_events(event);
                     }
-                    last_size = 1;
+                    needs_redraw = true;
                 }
+                continue;
+            }
+            if last_size == 0 {
+                sleep(Duration::from_millis(100));
+                last_size = 1;
+                needs_redraw = true;
+            }
 
-                match self.screen {
-                    AppScreen::MainMenu => {
-                        self.handle_events()?;
-                        let _ = terminal.draw(|frame| self.draw(frame));
-                    }
-                    AppScreen::PlayerNameInput { current, max } => {
-                        self.handle_player_name_input_events(current, max)?;
-                        let _ = terminal.draw(|frame| self.draw_player_name_input(frame, current));
-                    }
-                    AppScreen::Game => match self.current_game.as_mut() {
-                        Some(game) => {
-                            let continue_game = game.game_loop()?;
-                            if !continue_game {
-                                self.current_game = None;
-                                self.screen = AppScreen::MainMenu;
-                            } else {
-                                let _ = terminal.draw(|frame| game.draw(frame));
+            if matches!(self.screen, AppScreen::Game) {
+                // keep the court in step with live resizes before the next tick
+                if let Some(game) = self.current_game.as_mut() {
+                    let (game_area, _) =
+                        game::court_layout(Rect::new(0, 0, size.width, size.height));
+                    game.set_area(game_area);
+                }
+
+                // the match runs on its own clock and decides when to draw
+                match self.current_game.as_mut() {
+                    Some(game) => {
+                        let continue_game = game.game_loop()?;
+                        if !continue_game {
+                            if let Some(game) = self.current_game.take() {
+                                self.finish_game(&game);
                             }
-                        }
-                        None => {
                             self.screen = AppScreen::MainMenu;
+                            needs_redraw = true;
+                        } else if game.should_draw() {
+                            let _ = terminal.draw(|frame| game.draw(frame));
                         }
-                    },
-                    AppScreen::Settings => {
-                        self.handle_settings_events()?;
-                        let _ = terminal.draw(|frame| self.draw_settings(frame));
                     }
+                    None => {
+                        self.screen = AppScreen::MainMenu;
+                        needs_redraw = true;
+                    }
+                }
+                continue;
+            }
+
+            // menus only redraw after something happened
+            if needs_redraw {
+                let _ = terminal.draw(|frame| match self.screen {
+                    AppScreen::MainMenu => self.draw(frame),
+                    AppScreen::ProfilePicker(_) => self.draw_profile_picker(frame),
+                    AppScreen::Leaderboard => self.draw_leaderboard(frame),
+                    AppScreen::Settings => self.draw_settings(frame),
+                    AppScreen::Game => {}
+                });
+                needs_redraw = false;
+            }
+
+            if let Some(event) = Self::wait_for_event()? {
+                match self.screen {
+                    AppScreen::MainMenu => self.handle_events(event),
+                    AppScreen::ProfilePicker(game_type) => {
+                        self.handle_profile_picker_events(event, game_type)
+                    }
+                    AppScreen::Leaderboard => self.handle_leaderboard_events(event),
+                    AppScreen::Settings => self.handle_settings_events(event),
+                    AppScreen::Game => {}
                 }
+                needs_redraw = true;
             }
         }
 
         Ok(())
     }
 
+    // blocks until there's input or a resize; the timeout just makes sure a
+    // size change that never sent an event still gets noticed
+    fn wait_for_event() -> io::Result<Option<Event>> {
+        if event::poll(MENU_IDLE_TIMEOUT)? {
+            event::read().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
     fn show_terminal_resize_warning(&mut self, frame: &mut Frame) {
-        let colors = self.selected_theme.colors();
+        let colors = self.colors();
         let area = frame.area();
         let popup_area = centered_rect_with_percentage(60, 20, area.width, area.height);
         let popup = Paragraph::new("Terminal too small!\nPlease resize.")
             .block(
                 Block::default()
-                    .title("Warning")
+                    .title(Line::from("Warning").style(Style::default().fg(colors.title)))
                     .borders(Borders::ALL)
                     .border_type(BorderType::Thick),
             )
@@ -162,10 +664,13 @@ impl App {
     }
 
     fn draw(&mut self, frame: &mut Frame) {
+        let colors = self.colors();
+        // short terminals only get the menu, without the logo
+        let logo_height = if frame.area().height < 25 { 0 } else { 12 };
         let vertical_layout = Layout::default()
             .direction(Direction::Vertical)
             .constraints(vec![
-                Constraint::Length(12),
+                Constraint::Length(logo_height),
                 Constraint::Length(13),
                 Constraint::Max(5),
             ])
@@ -174,12 +679,12 @@ impl App {
 
         let big_text = BigText::builder()
             .pixel_size(PixelSize::Sextant)
-            .style(Style::new().blue())
+            .style(Style::new().fg(colors.title))
             .lines(vec![
                 "".into(),
-                "terminal".cyan().into(),
-                "PONG".white().into(),
-                "~~~~~".light_green().into(),
+                "terminal".fg(colors.title).into(),
+                "PONG".fg(colors.text).into(),
+                "~~~~~".fg(colors.accent).into(),
             ])
             .alignment(Alignment::Center)
             .build();
@@ -187,13 +692,15 @@ impl App {
 
         let options_block_layout = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints(vec![Constraint::Percentage(30)])
+            .constraints(vec![Constraint::Length(
+                (frame.area().width * 3 / 10).max(30),
+            )])
             .flex(Flex::Center)
             .split(vertical_layout[1]);
         frame.render_widget(
             Block::default()
                 .title("")
-                .style(Style::default().fg(Color::Cyan))
+                .style(Style::default().fg(colors.menu))
                 .borders(Borders::ALL)
                 .border_type(BorderType::Double),
             options_block_layout[0],
@@ -205,420 +712,790 @@ impl App {
             .flex(Flex::Center)
             .split(options_block_layout[0]);
 
-        let inner_options_layout = options_layout[0].inner(Margin::new(1, 0));
-        let rows_stored = inner_options_layout.height.clamp(5, 15) as usize;
+        let style = MenuStyle {
+            normal: Style::default().fg(colors.menu).bold(),
+            selected: self.selection_style().italic(),
+            markers: false,
+        };
+        self.main_menu
+            .draw(frame, options_layout[0].inner(Margin::new(1, 1)), &style);
 
-        let option_constraints = vec![Constraint::Max(1); rows_stored];
-        let option_areas = Layout::vertical(option_constraints)
-            .flex(Flex::Center)
-            .split(inner_options_layout);
+        self.draw_errors(frame, vertical_layout[2]);
+    }
 
-        let empty_line = Paragraph::new("")
-            .style(Style::default())
-            .alignment(Alignment::Center);
+    // config and theme file problems, shown instead of failing at startup
+    fn draw_errors(&self, frame: &mut Frame, area: Rect) {
+        let errors = self
+            .config_error
+            .iter()
+            .chain(self.profiles_error.iter())
+            .chain(self.theme_errors.iter())
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        if errors.is_empty() {
+            return;
+        }
+        let colors = self.colors();
+        let error_widget = Paragraph::new(errors.join("\n"))
+            .style(Style::default().fg(colors.ball))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+        frame.render_widget(error_widget, area);
+    }
+
+    fn draw_profile_picker(&mut self, frame: &mut Frame) {
+        let colors = self.colors();
+        let area = frame.area();
+        let selection = self.selection_style();
+        if let Some(editor) = self.profile_editor.as_mut() {
+            let popup_area = centered_rect(86, 20, area.width, area.height);
+            editor.draw(frame, popup_area, &colors, selection, self.color_support);
+            return;
+        }
+
+        let popup_area = centered_rect(56, 16, area.width, area.height);
+        let block = Block::default()
+            .title(Line::from("Who's Playing?").style(Style::default().fg(colors.title)))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick)
+            .style(Style::default().fg(colors.menu));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [menu_area, message_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(inner);
+        let style = MenuStyle {
+            normal: Style::default().fg(colors.text),
+            selected: selection.fg(colors.input),
+            markers: true,
+        };
+        let items = self.picker_items();
+        self.picker_menu.set_items(items);
+        self.picker_menu.draw(frame, menu_area, &style);
+        if let Some(message) = self.picker_message.as_ref() {
+            frame.render_widget(
+                Paragraph::new(message.as_str())
+                    .style(Style::default().fg(colors.ball).bold())
+                    .alignment(Alignment::Center),
+                message_area,
+            );
+        }
+    }
+
+    fn handle_events(&mut self, event: Event) {
+        let menu_event = match event {
+            Event::Key(key_event) if is_key_press(&key_event) => {
+                if key_event.code == KeyCode::Char('q') {
+                    self.exit();
+                    return;
+                }
+                self.main_menu.handle_key(key_event)
+            }
+            Event::Mouse(mouse_event) => self.main_menu.handle_mouse(mouse_event),
+            _ => return,
+        };
+        if let MenuEvent::Activate(action) = menu_event {
+            self.activate_main_menu(action);
+        }
+    }
 
-        frame.render_widget(empty_line.clone(), option_areas[0]);
-        for (i, &option) in self.main_menu.options.iter().enumerate() {
-            let mut option_widget = Paragraph::new(option)
-                .style(Style::default().fg(Color::Green).bold())
-                .alignment(Alignment::Center);
-
-            if i == self.main_menu.selected {
-                option_widget = option_widget.style(
-                    Style::default()
-                        .bg(Color::Reset)
-                        .fg(Color::White)
-                        .bold()
-                        .italic(),
+    fn activate_main_menu(&mut self, action: MainAction) {
+        match action {
+            MainAction::VsAi => self.pick_profiles(GameType::AgainstAi),
+            MainAction::WithFriend => self.pick_profiles(GameType::WithFriend),
+            MainAction::Watch => {
+                self.start_game(
+                    SCREENSAVER_NAMES,
+                    [None, None],
+                    GameType::ScreenSaver,
+                    self.default_difficulty_screensaver,
                 );
             }
+            MainAction::Leaderboard => {
+                self.leaderboard_message = None;
+                self.screen = AppScreen::Leaderboard;
+            }
+            MainAction::Settings => {
+                self.settings_menu.reset();
+                self.screen = AppScreen::Settings;
+            }
+            MainAction::Exit => self.exit(),
+        }
+    }
+
+    fn pick_profiles(&mut self, game_type: GameType) {
+        self.picker_menu = Menu::new(Vec::new());
+        self.picker_message = None;
+        self.screen = AppScreen::ProfilePicker(game_type);
+    }
 
-            frame.render_widget(option_widget, option_areas[(i + 1) * 2]);
+    fn human_players(&self) -> usize {
+        match self.screen {
+            AppScreen::ProfilePicker(GameType::WithFriend) => 2,
+            _ => 1,
         }
-        frame.render_widget(empty_line, option_areas[0]);
     }
 
-    fn draw_player_name_input(&mut self, frame: &mut Frame, current: usize) {
-        let area = frame.area();
-        let popup_area = centered_rect_with_percentage(60, 20, area.width, area.height);
-        let label = if current == 0 {
-            "Enter Player 1 name (max 16 chars):"
+    // a profile choice per human player, then start and profile management
+    fn picker_items(&self) -> Vec<MenuItem<PickerField>> {
+        let mut options = vec!["Guest".to_string()];
+        options.extend(self.profiles.iter().map(|profile| profile.name.clone()));
+        let mut items = (0..self.human_players())
+            .map(|i| {
+                MenuItem::new(
+                    format!("Player {}", i + 1),
+                    ItemKind::Choice {
+                        id: PickerField::Player(i),
+                        options: options.clone(),
+                        selected: self.picked_profiles[i].map_or(0, |index| index + 1),
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+        items.push(MenuItem::new(
+            "Match",
+            ItemKind::Choice {
+                id: PickerField::MatchLength,
+                options: self
+                    .match_lengths()
+                    .iter()
+                    .map(|length| match length {
+                        Some(points) => format!("First to {}", points),
+                        None => "Endless".to_string(),
+                    })
+                    .collect(),
+                selected: self
+                    .match_lengths()
+                    .iter()
+                    .position(|length| *length == self.match_first_to(self.picked_profiles))
+                    .unwrap_or(0),
+            },
+        ));
+        items.push(MenuItem::new("Start", ItemKind::Action(PickerField::Start)));
+        items.push(MenuItem::new(
+            "New Profile…",
+            ItemKind::Action(PickerField::NewProfile),
+        ));
+        if !self.profiles.is_empty() {
+            let mut profiles = self
+                .profiles
+                .iter()
+                .enumerate()
+                .map(|(i, profile)| {
+                    MenuItem::new(
+                        profile.name.clone(),
+                        ItemKind::Action(PickerField::EditProfile(i)),
+                    )
+                })
+                .collect::<Vec<_>>();
+            profiles.push(MenuItem::back());
+            items.push(MenuItem::new("Edit Profile", ItemKind::SubMenu(profiles)));
+        }
+        items.push(MenuItem::back());
+        items
+    }
+
+    // the usual lengths, plus one given with --first-to
+    fn match_lengths(&self) -> Vec<Option<u32>> {
+        let mut lengths = MATCH_LENGTHS.to_vec();
+        if !lengths.contains(&self.first_to) {
+            lengths.push(self.first_to);
+        }
+        lengths
+    }
+
+    // endless matches never finish and so are never rated; with a profile
+    // playing, a match is first to 5 unless a length was picked or given
+    fn match_first_to(&self, profiles: [Option<usize>; 2]) -> Option<u32> {
+        if let Some(first_to) = self.picked_first_to {
+            first_to
+        } else if self.first_to.is_some() || profiles.iter().all(Option::is_none) {
+            self.first_to
         } else {
-            "Enter Player 2 name (max 16 chars):"
-        };
-        let name = &self.name_input;
-        let input = format!("{}\n> {}", label, name);
-        let popup = Paragraph::new(input)
-            .block(
-                Block::default()
-                    .title("Player Names")
-                    .borders(Borders::ALL)
-                    .border_type(BorderType::Thick),
-            )
-            .style(Style::default().fg(Color::Green))
-            .alignment(Alignment::Center);
-        frame.render_widget(popup, popup_area);
+            Some(RATED_FIRST_TO)
+        }
     }
 
-    fn handle_events(&mut self) -> io::Result<()> {
-        // Non-blocking event polling with short timeout
-        if event::poll(Duration::from_millis(10))? {
-            match event::read()? {
-                Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
-                    match key_event.code {
-                        KeyCode::Char('q') => self.exit(),
-                        KeyCode::Up => {
-                            if self.main_menu.selected > 0 {
-                                self.main_menu.selected -= 1;
-                            } else {
-                                self.main_menu.selected = 4;
-                            }
-                        }
-                        KeyCode::Down => {
-                            if self.main_menu.selected < 4 {
-                                self.main_menu.selected += 1;
-                            } else {
-                                self.main_menu.selected = 0;
-                            }
-                        }
-                        KeyCode::Enter => {
-                            match self.main_menu.selected {
-                                0 => {
-                                    // Play vs. AI
-                                    self.name_input.clear();
-                                    self.player_names = [String::new(), String::new()];
-                                    self.screen = AppScreen::PlayerNameInput { current: 0, max: 0 };
-                                }
-                                1 => {
-                                    // Play with Friend
-                                    self.name_input.clear();
-                                    self.player_names = [String::new(), String::new()];
-                                    self.screen = AppScreen::PlayerNameInput { current: 0, max: 1 };
-                                }
-                                2 => {
-                                    // I like to watch
-                                    let mut game = Game::new(
-                                        ["Forg", "Car"],
-                                        Rect::default(),
-                                        GameType::ScreenSaver,
-                                        Some(self.default_difficulty_screensaver),
-                                    );
-                                    game.set_theme(self.selected_theme);
-                                    self.current_game = Some(game);
-                                    self.screen = AppScreen::Game;
-                                }
-                                3 => {
-                                    // Settings
-                                    self.settings_selected = 0;
-                                    self.screen = AppScreen::Settings;
-                                }
-                                4 => {
-                                    self.exit();
-                                }
-                                _ => {}
-                            }
+    fn open_profile_editor(&mut self, index: Option<usize>) {
+        let profile = index.map_or_else(Profile::default, |index| self.profiles[index].clone());
+        let taken_names = self
+            .profiles
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| Some(*i) != index)
+            .map(|(_, profile)| profile.name.clone())
+            .collect();
+        self.profile_editor = Some(ProfileEditor::new(
+            profile,
+            index,
+            taken_names,
+            &self.key_bindings,
+        ));
+        self.picker_message = None;
+    }
+
+    fn start_picked_match(&mut self, game_type: GameType) {
+        let picked = self.picked_profiles;
+        if game_type == GameType::WithFriend && picked[0].is_some() && picked[0] == picked[1] {
+            self.picker_message = Some("Pick a different profile for each player".to_string());
+            return;
+        }
+        let default_names = ["Player 1", "Player 2"];
+        let names = [0, 1].map(|i| match picked[i] {
+            Some(index) => self.profiles[index].name.clone(),
+            None => default_names[i].to_string(),
+        });
+        if game_type == GameType::WithFriend {
+            self.start_game(
+                [names[0].as_str(), names[1].as_str()],
+                picked,
+                GameType::WithFriend,
+                self.default_difficulty_with_friend,
+            );
+        } else {
+            self.start_game(
+                [names[0].as_str(), COMPUTER_NAME],
+                [picked[0], None],
+                GameType::AgainstAi,
+                self.default_difficulty_vs_ai,
+            );
+        }
+    }
+
+    fn handle_profile_editor_action(&mut self, action: ProfileAction) {
+        let Some(editor) = self.profile_editor.take() else {
+            return;
+        };
+        match action {
+            ProfileAction::None => self.profile_editor = Some(editor),
+            ProfileAction::Save => {
+                match editor.index {
+                    Some(index) => self.profiles[index] = editor.profile,
+                    None => {
+                        self.profiles.push(editor.profile);
+                        // a new profile takes the first seat still played by a guest
+                        let humans = self.human_players();
+                        if let Some(slot) = self.picked_profiles[..humans]
+                            .iter()
+                            .position(Option::is_none)
+                        {
+                            self.picked_profiles[slot] = Some(self.profiles.len() - 1);
                         }
-                        _ => {}
                     }
                 }
-                _ => {}
+                self.save_profiles();
             }
+            ProfileAction::Delete => {
+                if let Some(deleted) = editor.index {
+                    self.profiles.remove(deleted);
+                    for picked in self.picked_profiles.iter_mut() {
+                        *picked = match *picked {
+                            Some(index) if index == deleted => None,
+                            Some(index) if index > deleted => Some(index - 1),
+                            other => other,
+                        };
+                    }
+                    self.picker_menu.reset();
+                    self.save_profiles();
+                }
+            }
+            ProfileAction::Cancel => {}
         }
-        Ok(())
     }
 
-    fn handle_player_name_input_events(&mut self, current: usize, max: usize) -> io::Result<()> {
-        if event::poll(Duration::from_millis(10))? {
-            match event::read()? {
-                Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
-                    match key_event.code {
-                        KeyCode::Enter => {
-                            let default_names = ["Player 1", "Player 2"];
-                            let name = if self.name_input.trim().is_empty() {
-                                default_names[current]
-                            } else {
-                                self.name_input.trim()
-                            };
-                            self.player_names[current] = name.to_string();
-                            self.name_input.clear();
-                            if current < max {
-                                self.screen = AppScreen::PlayerNameInput {
-                                    current: current + 1,
-                                    max,
-                                };
-                            } else {
-                                if max == 0 {
-                                    // vs AI
-                                    let mut game = Game::new(
-                                        [self.player_names[0].as_str(), "Computer"],
-                                        Rect::default(),
-                                        GameType::AgainstAi,
-                                        Some(self.default_difficulty_vs_ai),
-                                    );
-                                    game.set_theme(self.selected_theme);
-                                    self.current_game = Some(game);
-                                } else {
-                                    // with friend
-                                    let mut game = Game::new(
-                                        [
-                                            self.player_names[0].as_str(),
-                                            self.player_names[1].as_str(),
-                                        ],
-                                        Rect::default(),
-                                        GameType::WithFriend,
-                                        Some(self.default_difficulty_with_friend),
-                                    );
-                                    game.set_theme(self.selected_theme);
-                                    self.current_game = Some(game);
-                                }
-                                self.screen = AppScreen::Game;
-                            }
-                        }
-                        KeyCode::Esc => {
-                            self.screen = AppScreen::MainMenu;
-                        }
-                        KeyCode::Backspace => {
-                            self.name_input.pop();
-                        }
-                        KeyCode::Char(c) => {
-                            if self.name_input.len() < PLAYER_NAME_CHAR_LEN && c.is_ascii_graphic()
-                            {
-                                self.name_input.push(c);
-                            }
-                        }
-                        _ => {}
-                    }
+    fn handle_profile_picker_events(&mut self, event: Event, game_type: GameType) {
+        if let Some(editor) = self.profile_editor.as_mut() {
+            let action = match event {
+                Event::Key(key_event) if is_key_press(&key_event) => editor.handle_key(key_event),
+                Event::Mouse(mouse_event) => editor.handle_mouse(mouse_event),
+                _ => return,
+            };
+            self.handle_profile_editor_action(action);
+            return;
+        }
+
+        let menu_event = match event {
+            Event::Key(key_event) if is_key_press(&key_event) => {
+                self.picker_menu.handle_key(key_event)
+            }
+            Event::Mouse(mouse_event) => self.picker_menu.handle_mouse(mouse_event),
+            _ => return,
+        };
+        match menu_event {
+            MenuEvent::Choice(PickerField::Player(i), selected) => {
+                self.picked_profiles[i] = selected.checked_sub(1);
+                self.picker_message = None;
+            }
+            MenuEvent::Choice(PickerField::MatchLength, selected) => {
+                self.picked_first_to = Some(self.match_lengths()[selected]);
+            }
+            MenuEvent::Activate(PickerField::Start) => self.start_picked_match(game_type),
+            MenuEvent::Activate(PickerField::NewProfile) => self.open_profile_editor(None),
+            MenuEvent::Activate(PickerField::EditProfile(index)) => {
+                self.open_profile_editor(Some(index))
+            }
+            MenuEvent::Back => self.screen = AppScreen::MainMenu,
+            _ => {}
+        }
+    }
+
+    // --- Leaderboard Screen ---
+    fn draw_leaderboard(&mut self, frame: &mut Frame) {
+        let colors = self.colors();
+        let area = frame.area();
+        let popup_area = centered_rect(70, 22, area.width, area.height);
+        let block = Block::default()
+            .title(Line::from("Leaderboard").style(Style::default().fg(colors.title)))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick)
+            .style(Style::default().fg(colors.menu));
+        let inner = block.inner(popup_area).inner(Margin::new(1, 1));
+        frame.render_widget(block, popup_area);
+
+        let [table_area, message_area, hint_area] = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(inner);
+        let text = Style::default().fg(colors.text);
+        if self.profiles.is_empty() {
+            frame.render_widget(
+                Paragraph::new("No profiles yet. Create one when starting a match.")
+                    .style(text)
+                    .alignment(Alignment::Center),
+                table_area,
+            );
+        } else {
+            let rows = rating::leaderboard(&self.profiles)
+                .into_iter()
+                .enumerate()
+                .map(|(rank, index)| {
+                    let profile = &self.profiles[index];
+                    let (wins, losses) = rating::record(profile);
+                    let form = rating::form(profile);
+                    Row::new(vec![
+                        (rank + 1).to_string(),
+                        profile.name.clone(),
+                        format!("{:.0}", profile.rating),
+                        format!("{}–{}", wins, losses),
+                        if form.is_empty() {
+                            "-".to_string()
+                        } else {
+                            form
+                        },
+                    ])
+                })
+                .collect::<Vec<_>>();
+            let table = Table::new(
+                rows,
+                [
+                    Constraint::Length(4),
+                    Constraint::Min(16),
+                    Constraint::Length(7),
+                    Constraint::Length(8),
+                    Constraint::Length(rating::FORM_LENGTH as u16 + 1),
+                ],
+            )
+            .header(
+                Row::new(vec!["#", "Player", "Rating", "W–L", "Form"])
+                    .style(Style::default().fg(colors.accent).bold())
+                    .bottom_margin(1),
+            )
+            .style(text);
+            frame.render_widget(table, table_area);
+        }
+
+        if let Some(message) = self.leaderboard_message.as_ref() {
+            frame.render_widget(
+                Paragraph::new(message.as_str())
+                    .style(Style::default().fg(colors.ball))
+                    .alignment(Alignment::Center),
+                message_area,
+            );
+        }
+        frame.render_widget(
+            Paragraph::new("[E] Export rating history  [Esc] Back")
+                .style(text)
+                .alignment(Alignment::Center),
+            hint_area,
+        );
+    }
+
+    fn export_rating_history(&mut self) {
+        let Some(profiles_path) = self.profiles_path.as_deref() else {
+            self.leaderboard_message = Some("Profiles aren't being saved".to_string());
+            return;
+        };
+        let path = profile::history_export_path(profiles_path);
+        self.leaderboard_message = Some(match rating::export_history(&path, &self.profiles) {
+            Ok(count) => format!("Exported {} rated matches to {}", count, path.display()),
+            Err(e) => e,
+        });
+    }
+
+    fn handle_leaderboard_events(&mut self, event: Event) {
+        match event {
+            Event::Key(key_event) if is_key_press(&key_event) => match key_event.code {
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => {
+                    self.screen = AppScreen::MainMenu
                 }
+                KeyCode::Char('e') | KeyCode::Char('E') => self.export_rating_history(),
                 _ => {}
-            }
+            },
+            Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Right),
+                ..
+            }) => self.screen = AppScreen::MainMenu,
+            _ => {}
         }
-        Ok(())
     }
 
     // --- Settings Screen ---
-    fn draw_settings(&mut self, frame: &mut Frame) {
-        let colors = self.selected_theme.colors();
-        let area = frame.area();
-        let theme_names = [
-            "Monokai",
-            "Solarized",
-            "Dracula",
-            "Gruvbox Dark",
-            "Nord",
-            "One Dark",
-            "High Contrast",
-        ];
-        let theme_idx = self.selected_theme as usize;
-        let settings = [
-            format!(
-                "Default Difficulty (vs AI): {:.2}",
-                self.default_difficulty_vs_ai
+    fn settings_items(&self) -> Vec<MenuItem<Setting>> {
+        let difficulty = |label: &str, id, value| {
+            MenuItem::new(
+                label,
+                ItemKind::Slider {
+                    id,
+                    value,
+                    min: 0.0,
+                    max: 2.0,
+                    step: 0.1,
+                },
+            )
+        };
+        let choice = |label: &str, id, options: Vec<String>, selected| {
+            MenuItem::new(
+                label,
+                ItemKind::Choice {
+                    id,
+                    options,
+                    selected,
+                },
+            )
+        };
+        let on_off = || vec!["Off".to_string(), "On".to_string()];
+        vec![
+            MenuItem::new(
+                "Default Difficulty",
+                ItemKind::SubMenu(vec![
+                    difficulty(
+                        "vs AI",
+                        Setting::DifficultyVsAi,
+                        self.default_difficulty_vs_ai,
+                    ),
+                    difficulty(
+                        "with Friend",
+                        Setting::DifficultyWithFriend,
+                        self.default_difficulty_with_friend,
+                    ),
+                    difficulty(
+                        "Screensaver",
+                        Setting::DifficultyScreensaver,
+                        self.default_difficulty_screensaver,
+                    ),
+                    MenuItem::back(),
+                ]),
             ),
-            format!(
-                "Default Difficulty (with Friend): {:.2}",
-                self.default_difficulty_with_friend
+            MenuItem::new(
+                "Display",
+                ItemKind::SubMenu(vec![
+                    choice(
+                        "Theme",
+                        Setting::Theme,
+                        self.themes.iter().map(|theme| theme.name.clone()).collect(),
+                        self.selected_theme,
+                    ),
+                    MenuItem::new("Edit Theme…", ItemKind::Action(Setting::EditTheme)),
+                    choice(
+                        "Renderer",
+                        Setting::Renderer,
+                        RenderMode::ALL
+                            .iter()
+                            .map(|mode| mode.name().to_string())
+                            .collect(),
+                        RenderMode::ALL
+                            .iter()
+                            .position(|mode| *mode == self.render_mode)
+                            .unwrap_or(0),
+                    ),
+                    choice(
+                        "Reduced Motion",
+                        Setting::ReducedMotion,
+                        on_off(),
+                        self.reduced_motion as usize,
+                    ),
+                    MenuItem::back(),
+                ]),
             ),
-            format!(
-                "Default Difficulty (Screensaver): {:.2}",
-                self.default_difficulty_screensaver
+            MenuItem::new(
+                "Controls",
+                ItemKind::SubMenu(vec![
+                    choice(
+                        "Mouse Control",
+                        Setting::MouseControl,
+                        vec!["Wheel".to_string(), "Follow Pointer".to_string()],
+                        self.mouse_control as usize,
+                    ),
+                    MenuItem::new("Key Bindings…", ItemKind::Action(Setting::KeyBindings)),
+                    MenuItem::back(),
+                ]),
             ),
-            format!("Theme: {}", theme_names[theme_idx]),
-            "Back".to_string(),
-        ];
-
-        let mut styled_lines = Vec::new();
-        for (i, s) in settings.iter().enumerate() {
-            if i == self.settings_selected {
-                styled_lines.push(
-                    Paragraph::new(format!("> {} <", s))
-                        .style(Style::default().fg(Color::White).bold())
-                        .alignment(Alignment::Center),
-                );
-            } else {
-                styled_lines.push(
-                    Paragraph::new(format!("  {}  ", s))
-                        .style(Style::default().fg(colors.text))
-                        .alignment(Alignment::Center),
-                );
-            }
-        }
+            MenuItem::back(),
+        ]
+    }
 
-        let [settings_area] = Layout::horizontal([Constraint::Percentage(50)])
+    fn draw_settings(&mut self, frame: &mut Frame) {
+        let colors = self.colors();
+        let area = frame.area();
+        // rebuilt every frame so the values always match the settings
+        let items = self.settings_items();
+        self.settings_menu.set_items(items);
+
+        let [settings_area] = Layout::horizontal([Constraint::Length((area.width / 2).max(60))])
             .flex(Flex::Center)
             .areas(area);
-        let [settings_block_area, preview_area] =
-            Layout::vertical([Constraint::Length(12), Constraint::Length(3)])
-                .flex(Flex::Center)
-                .areas(settings_area);
+        let [settings_block_area, preview_area, error_area] = Layout::vertical([
+            Constraint::Length(16),
+            Constraint::Length(5),
+            Constraint::Length(3),
+        ])
+        .flex(Flex::Center)
+        .areas(settings_area);
+        let title = std::iter::once("Settings")
+            .chain(self.settings_menu.open_sub_menus())
+            .collect::<Vec<_>>()
+            .join(" › ");
         let settings_block = Block::default()
-            .title("Settings")
+            .title(Line::from(title).style(Style::default().fg(colors.title)))
             .borders(Borders::ALL)
             .border_type(BorderType::Thick)
             .style(Style::default().fg(colors.accent));
-        frame.render_widget(settings_block, settings_block_area);
-
-        let line_height = 2;
-        let total_height = styled_lines.len() * line_height;
-        let start_y = settings_block_area.y
-            + (settings_block_area
-                .height
-                .saturating_sub(total_height as u16)
-                / 2);
-        for (i, para) in styled_lines.into_iter().enumerate() {
-            let y = start_y + (i as u16) * line_height as u16;
-            let line_area = Rect {
-                x: settings_block_area.x + 2,
-                y,
-                width: settings_block_area.width.saturating_sub(4),
-                height: 1,
-            };
-            frame.render_widget(para, line_area);
-        }
-
-        let preview_colors = [
-            ("Player Bar", colors.player_bar),
-            ("Power Bar", colors.player_bar_power),
-            ("Ball", colors.ball),
-            ("Text", colors.text),
-            ("Accent", colors.accent),
-            ("Border", colors.border),
-            ("Background", colors.background),
-        ];
-        let color_bar_width = preview_area.width.saturating_sub(4);
-        let color_block_width = if preview_colors.len() > 0 {
-            color_bar_width / preview_colors.len() as u16
-        } else {
-            1
+
+        // the editors take over the settings block, the theme editor
+        // previews its own colors
+        let preview_of = |theme_colors: &ThemeColors| {
+            theme_colors
+                .simulated(self.cvd_preview)
+                .for_support(self.color_support)
         };
-        for (i, (_, color)) in preview_colors.iter().enumerate() {
-            let x = preview_area.x + 2 + (i as u16) * color_block_width;
-            let width = if i == preview_colors.len() - 1 {
-                color_bar_width - (color_block_width * (preview_colors.len() as u16 - 1))
+        let (preview, editing_slot) = match (&self.theme_editor, &self.bindings_editor) {
+            (Some(editor), _) => {
+                let preview = preview_of(&editor.colors);
+                editor.draw(frame, settings_block_area, &colors, &preview);
+                (preview, Some(editor.slot))
+            }
+            (None, Some(editor)) => {
+                editor.draw(frame, settings_block_area, &colors, self.selection_style());
+                (preview_of(&self.themes[self.selected_theme].colors), None)
+            }
+            (None, None) => {
+                let menu_area = settings_block.inner(settings_block_area);
+                frame.render_widget(settings_block, settings_block_area);
+                let style = MenuStyle {
+                    normal: Style::default().fg(colors.text),
+                    selected: self.selection_style(),
+                    markers: true,
+                };
+                self.settings_menu
+                    .draw(frame, menu_area.inner(Margin::new(1, 0)), &style);
+                (preview_of(&self.themes[self.selected_theme].colors), None)
+            }
+        };
+
+        let color_mode = format!(
+            "Color mode: {} ({})  ·  [V] Preview as: {}",
+            self.color_support.name(),
+            if self.color_support_forced {
+                "--colors"
             } else {
-                color_block_width
-            };
-            let color_rect = Rect {
-                x,
-                y: preview_area.y + 1,
-                width: width.max(1),
+                "detected"
+            },
+            self.cvd_preview.name()
+        );
+        frame.render_widget(
+            Paragraph::new(color_mode)
+                .style(Style::default().fg(colors.text))
+                .alignment(Alignment::Center),
+            Rect {
+                height: 1,
+                ..preview_area
+            },
+        );
+
+        let preview_colors = ThemeColors::SLOT_NAMES
+            .into_iter()
+            .zip(preview.slots())
+            .collect::<Vec<_>>();
+        let color_bar_width = preview_area.width.saturating_sub(4);
+        let color_block_width = color_bar_width / PREVIEW_SLOTS_PER_ROW as u16;
+        // one swatch row plus one label row per chunk of slots
+        self.swatch_areas.clear();
+        for (row, chunk) in preview_colors.chunks(PREVIEW_SLOTS_PER_ROW).enumerate() {
+            let swatch_y = preview_area.y + 1 + row as u16 * 2;
+            let row_width = color_block_width * chunk.len() as u16;
+            let row_x = preview_area.x + 2 + (color_bar_width - row_width) / 2;
+            for (i, (_, color)) in chunk.iter().enumerate() {
+                let color_rect = Rect {
+                    x: row_x + (i as u16) * color_block_width,
+                    y: swatch_y,
+                    width: color_block_width.max(1),
+                    height: 1,
+                };
+                let color_block = Paragraph::new("")
+                    .style(Style::default().bg(*color))
+                    .alignment(Alignment::Center);
+                frame.render_widget(color_block, color_rect);
+                // the label underneath picks the slot too
+                self.swatch_areas.push(Rect {
+                    height: 2,
+                    ..color_rect
+                });
+            }
+
+            let label_area = Rect {
+                x: row_x,
+                y: swatch_y + 1,
+                width: row_width,
                 height: 1,
             };
-            let color_block = Paragraph::new("")
-                .style(Style::default().bg(*color))
-                .alignment(Alignment::Center);
-            frame.render_widget(color_block, color_rect);
+            let label_spans = chunk
+                .iter()
+                .enumerate()
+                .map(|(i, (label, _))| {
+                    let text = format!("{:^width$}", label, width = color_block_width as usize);
+                    if editing_slot == Some(row * PREVIEW_SLOTS_PER_ROW + i) {
+                        Span::styled(text, Style::default().fg(colors.accent).bold().underlined())
+                    } else {
+                        Span::raw(text)
+                    }
+                })
+                .collect::<Vec<_>>();
+            let label_para =
+                Paragraph::new(Line::from(label_spans)).style(Style::default().fg(colors.text));
+            frame.render_widget(label_para, label_area);
         }
 
-        let label_area = Rect {
-            x: preview_area.x + 2,
-            y: preview_area.y + 2,
-            width: color_bar_width,
-            height: 1,
+        self.draw_errors(frame, error_area);
+    }
+
+    fn handle_theme_editor_key(&mut self, key_event: KeyEvent) {
+        let Some(editor) = self.theme_editor.as_mut() else {
+            return;
         };
-        let label_text = preview_colors
-            .iter()
-            .map(|(label, _)| format!("{:^width$}", label, width = color_block_width as usize))
-            .collect::<Vec<_>>()
-            .join("");
-        let label_para = Paragraph::new(label_text)
-            .style(Style::default().fg(colors.text))
-            .alignment(Alignment::Center);
-        frame.render_widget(label_para, label_area);
-    }
-
-    fn handle_settings_events(&mut self) -> io::Result<()> {
-        use crate::game_theme::GameTheme;
-        if event::poll(Duration::from_millis(10))? {
-            match event::read()? {
-                Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
-                    match key_event.code {
-                        KeyCode::Up => {
-                            if self.settings_selected > 0 {
-                                self.settings_selected -= 1;
-                            } else {
-                                self.settings_selected = 4;
-                            }
-                        }
-                        KeyCode::Down => {
-                            if self.settings_selected < 4 {
-                                self.settings_selected += 1;
-                            } else {
-                                self.settings_selected = 0;
-                            }
-                        }
-                        KeyCode::Left => match self.settings_selected {
-                            0 => {
-                                self.default_difficulty_vs_ai =
-                                    (self.default_difficulty_vs_ai - 0.1).clamp(0.0, 2.0)
-                            }
-                            1 => {
-                                self.default_difficulty_with_friend =
-                                    (self.default_difficulty_with_friend - 0.1).clamp(0.0, 2.0)
-                            }
-                            2 => {
-                                self.default_difficulty_screensaver =
-                                    (self.default_difficulty_screensaver - 0.1).clamp(0.0, 2.0)
-                            }
-                            3 => {
-                                let idx = self.selected_theme as usize;
-                                let new_idx = if idx == 0 { 6 } else { idx - 1 };
-                                self.selected_theme = match new_idx {
-                                    0 => GameTheme::Monokai,
-                                    1 => GameTheme::Solarized,
-                                    2 => GameTheme::Dracula,
-                                    3 => GameTheme::GruvboxDark,
-                                    4 => GameTheme::Nord,
-                                    5 => GameTheme::OneDark,
-                                    6 => GameTheme::HighContrast,
-                                    _ => GameTheme::Monokai,
-                                };
-                            }
-                            _ => {}
-                        },
-                        KeyCode::Right => match self.settings_selected {
-                            0 => {
-                                self.default_difficulty_vs_ai =
-                                    (self.default_difficulty_vs_ai + 0.1).clamp(0.0, 2.0)
-                            }
-                            1 => {
-                                self.default_difficulty_with_friend =
-                                    (self.default_difficulty_with_friend + 0.1).clamp(0.0, 2.0)
-                            }
-                            2 => {
-                                self.default_difficulty_screensaver =
-                                    (self.default_difficulty_screensaver + 0.1).clamp(0.0, 2.0)
-                            }
-                            3 => {
-                                let idx = self.selected_theme as usize;
-                                let new_idx = if idx == 6 { 0 } else { idx + 1 };
-                                self.selected_theme = match new_idx {
-                                    0 => GameTheme::Monokai,
-                                    1 => GameTheme::Solarized,
-                                    2 => GameTheme::Dracula,
-                                    3 => GameTheme::GruvboxDark,
-                                    4 => GameTheme::Nord,
-                                    5 => GameTheme::OneDark,
-                                    6 => GameTheme::HighContrast,
-                                    _ => GameTheme::Monokai,
-                                };
-                            }
-                            _ => {}
-                        },
-                        KeyCode::Enter => {
-                            if self.settings_selected == 4 {
-                                self.screen = AppScreen::MainMenu;
-                            }
-                        }
-                        KeyCode::Esc => {
-                            self.screen = AppScreen::MainMenu;
-                        }
-                        _ => {}
+        match editor.handle_key(key_event) {
+            EditorAction::None => {}
+            EditorAction::Close => self.theme_editor = None,
+            EditorAction::Save(theme) => {
+                let result = if find_theme(&self.themes, &theme.name).is_some() {
+                    Err(format!("A theme named \"{}\" already exists", theme.name))
+                } else {
+                    match self.config_path.as_deref() {
+                        Some(path) => theme_file::save_theme(&Config::themes_dir(path), &theme),
+                        None => Err("No config directory to save themes in".to_string()),
                     }
+                };
+                match result {
+                    Ok(_) => {
+                        self.themes.push(theme);
+                        self.selected_theme = self.themes.len() - 1;
+                        self.theme_editor = None;
+                    }
+                    Err(e) => editor.error = Some(e),
                 }
-                _ => {}
             }
         }
-        Ok(())
+    }
+
+    fn handle_bindings_editor_key(&mut self, key_event: KeyEvent) {
+        let Some(editor) = self.bindings_editor.as_mut() else {
+            return;
+        };
+        match editor.handle_key(key_event) {
+            BindingsAction::None => {}
+            BindingsAction::Close => {
+                self.key_bindings = editor.bindings.clone();
+                self.bindings_editor = None;
+            }
+        }
+    }
+
+    fn apply_setting(&mut self, menu_event: MenuEvent<Setting>) {
+        match menu_event {
+            MenuEvent::Slider(Setting::DifficultyVsAi, value) => {
+                self.default_difficulty_vs_ai = value
+            }
+            MenuEvent::Slider(Setting::DifficultyWithFriend, value) => {
+                self.default_difficulty_with_friend = value
+            }
+            MenuEvent::Slider(Setting::DifficultyScreensaver, value) => {
+                self.default_difficulty_screensaver = value
+            }
+            MenuEvent::Choice(Setting::Theme, index) => self.selected_theme = index,
+            MenuEvent::Choice(Setting::Renderer, index) => {
+                self.render_mode = RenderMode::ALL[index]
+            }
+            MenuEvent::Choice(Setting::ReducedMotion, index) => self.reduced_motion = index == 1,
+            MenuEvent::Choice(Setting::MouseControl, index) => self.mouse_control = index == 1,
+            MenuEvent::Activate(Setting::EditTheme) => {
+                self.theme_editor = Some(ThemeEditor::new(&self.themes[self.selected_theme]));
+            }
+            MenuEvent::Activate(Setting::KeyBindings) => {
+                self.bindings_editor = Some(BindingsEditor::new(&self.key_bindings));
+            }
+            MenuEvent::Back => self.leave_settings(),
+            _ => {}
+        }
+    }
+
+    fn handle_settings_mouse(&mut self, mouse_event: MouseEvent) {
+        if self.bindings_editor.is_some() {
+            return;
+        }
+        if let MouseEventKind::Down(MouseButton::Left) = mouse_event.kind {
+            if let Some(slot) = hit_test(&self.swatch_areas, mouse_event) {
+                // clicking a color edits that slot of the current theme
+                let editor = self
+                    .theme_editor
+                    .get_or_insert_with(|| ThemeEditor::new(&self.themes[self.selected_theme]));
+                editor.select_slot(slot);
+                return;
+            }
+        }
+        if self.theme_editor.is_none() {
+            let menu_event = self.settings_menu.handle_mouse(mouse_event);
+            self.apply_setting(menu_event);
+        }
+    }
+
+    fn handle_settings_events(&mut self, event: Event) {
+        let is_naming = self
+            .theme_editor
+            .as_ref()
+            .is_some_and(ThemeEditor::is_naming);
+        match event {
+            // the bindings editor gets every key, since any of them can be bound
+            Event::Key(key_event) if is_key_press(&key_event) && self.bindings_editor.is_some() => {
+                self.handle_bindings_editor_key(key_event);
+            }
+            Event::Key(key_event)
+                if is_key_press(&key_event)
+                    && key_event.code == KeyCode::Char('v')
+                    && !is_naming =>
+            {
+                self.cvd_preview = self.cvd_preview.next();
+            }
+            Event::Key(key_event) if is_key_press(&key_event) && self.theme_editor.is_some() => {
+                self.handle_theme_editor_key(key_event);
+            }
+            Event::Key(key_event) if is_key_press(&key_event) => {
+                let menu_event = self.settings_menu.handle_key(key_event);
+                self.apply_setting(menu_event);
+            }
+            Event::Mouse(mouse_event) => self.handle_settings_mouse(mouse_event),
+            _ => {}
+        }
     }
 
     fn exit(&mut self) {
@@ -626,15 +1503,82 @@ impl App {
     }
 }
 
+// index of the area under the pointer, if any
+fn hit_test(areas: &[Rect], mouse_event: MouseEvent) -> Option<usize> {
+    let position = Position::new(mouse_event.column, mouse_event.row);
+    areas.iter().position(|area| area.contains(position))
+}
+
 fn main() -> io::Result<()> {
-    let terminal = ratatui::init();
-    let mut app = App::new();
+    // everything that can fail on bad input happens before raw mode
+    let options = match cli::parse_args(env::args().skip(1)) {
+        Ok(CliCommand::Run(options)) => options,
+        Ok(CliCommand::Help) => {
+            print!("{}", cli::USAGE);
+            return Ok(());
+        }
+        Ok(CliCommand::Version) => {
+            println!("terminal-pong {}", env!("CARGO_PKG_VERSION"));
+            return Ok(());
+        }
+        Err(e) => {
+            eprintln!("error: {}\n\nRun with --help for usage.", e);
+            std::process::exit(2);
+        }
+    };
 
+    let config_path = options.config_path.or_else(Config::default_path);
+    let (config, config_error) = match config_path.as_deref().map(Config::load) {
+        Some(Ok(config)) => (config, None),
+        Some(Err(e)) => (
+            Config::default(),
+            Some(format!("{} (settings won't be saved)", e)),
+        ),
+        None => (Config::default(), None),
+    };
+
+    // keep a file that couldn't be read as it is rather than overwrite it
+    let config_writable = config_error.is_none();
+    let mut app = App::new(config, config_path, config_error);
+    app.config_writable = config_writable;
+    if let Some(theme) = options.theme.as_deref() {
+        if let Err(e) = app.select_theme(theme) {
+            eprintln!("error: {}\n\nRun with --help for usage.", e);
+            std::process::exit(2);
+        }
+    }
+    if let Some(color_support) = options.color_support {
+        app.color_support = color_support;
+        app.color_support_forced = true;
+    }
+    app.load_profiles(options.profiles_path.or_else(profile::default_path));
+    app.seed = options.seed;
+    app.first_to = options.first_to;
+    if let Some(game_type) = options.mode {
+        app.launch(game_type, options.player_names, options.difficulty);
+    }
+
+    let terminal = ratatui::init();
     let mut stdout = io::stdout();
     stdout.execute(event::EnableMouseCapture)?;
+    // pauses the match when switching away from the terminal
+    stdout.execute(event::EnableFocusChange)?;
+    // kitty-style key release events, where the terminal supports them
+    let keyboard_enhanced = terminal::supports_keyboard_enhancement().unwrap_or(false);
+    if keyboard_enhanced {
+        stdout.execute(PushKeyboardEnhancementFlags(
+            KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+                | KeyboardEnhancementFlags::REPORT_EVENT_TYPES,
+        ))?;
+        app.set_key_release_events(true);
+    }
 
     let app_result = app.run(terminal);
 
+    if keyboard_enhanced {
+        stdout.execute(PopKeyboardEnhancementFlags)?;
+    }
+    stdout.execute(event::DisableFocusChange)?;
     stdout.lock().execute(event::DisableMouseCapture)?;
 
     ratatui::restore();
@@ -0,0 +1,683 @@
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    layout::{Alignment, Position, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// What an entry does. `T` identifies the entry to whoever owns the menu.
+#[derive(Debug, Clone)]
+pub enum ItemKind<T> {
+    /// Enter or a click reports `MenuEvent::Activate`.
+    Action(T),
+    /// ←/→ (or the wheel) step a number within `min..=max`.
+    Slider {
+        id: T,
+        value: f32,
+        min: f32,
+        max: f32,
+        step: f32,
+    },
+    /// ←/→ and Enter cycle through named options.
+    Choice {
+        id: T,
+        options: Vec<String>,
+        selected: usize,
+    },
+    /// Typing edits the value directly while the entry is selected, with
+    /// ←/→, Home and End moving the cursor. The value is kept within both
+    /// `max_graphemes` user-perceived characters and `max_width` columns.
+    Text {
+        id: T,
+        value: String,
+        max_graphemes: usize,
+        max_width: usize,
+    },
+    SubMenu(Vec<MenuItem<T>>),
+    /// Leaves the current sub-menu, or the whole menu at the top level.
+    Back,
+}
+
+#[derive(Debug, Clone)]
+pub struct MenuItem<T> {
+    pub label: String,
+    pub kind: ItemKind<T>,
+}
+
+impl<T> MenuItem<T> {
+    pub fn new(label: impl Into<String>, kind: ItemKind<T>) -> Self {
+        Self {
+            label: label.into(),
+            kind,
+        }
+    }
+
+    pub fn back() -> Self {
+        Self::new("Back", ItemKind::Back)
+    }
+
+    // the entry as shown, e.g. "Renderer: Braille"
+    fn text(&self) -> String {
+        match &self.kind {
+            ItemKind::Slider { value, step, .. } if step.fract() == 0.0 => {
+                format!("{}: {:.0}", self.label, value)
+            }
+            ItemKind::Slider { value, .. } => format!("{}: {:.2}", self.label, value),
+            ItemKind::Choice {
+                options, selected, ..
+            } => format!(
+                "{}: {}",
+                self.label,
+                options.get(*selected).map(String::as_str).unwrap_or("")
+            ),
+            ItemKind::Text { value, .. } => format!("{}: {}", self.label, value),
+            ItemKind::SubMenu(_) => format!("{} ›", self.label),
+            ItemKind::Action(_) | ItemKind::Back => self.label.clone(),
+        }
+    }
+}
+
+/// What the owner of a menu has to act on after an input.
+#[derive(Debug, PartialEq)]
+pub enum MenuEvent<T> {
+    None,
+    Activate(T),
+    Slider(T, f32),
+    Choice(T, usize),
+    Text(T, String),
+    Back,
+}
+
+/// How entries are drawn. `markers` puts "> <" around the selected one, for
+/// when the selection style alone might not be visible enough.
+pub struct MenuStyle {
+    pub normal: Style,
+    pub selected: Style,
+    pub markers: bool,
+}
+
+/// A vertical list of entries with keyboard and mouse navigation. The menu
+/// only edits its own copy of the values and reports changes as events; the
+/// owner applies them and hands back fresh items with `set_items`.
+#[derive(Debug)]
+pub struct Menu<T> {
+    items: Vec<MenuItem<T>>,
+    path: Vec<usize>, // selected entry on each open level, outermost first
+    areas: Vec<Rect>, // where the current level's entries were last drawn
+    cursor: usize,    // in graphemes, within the selected text entry
+}
+
+impl<T: Clone> Menu<T> {
+    pub fn new(items: Vec<MenuItem<T>>) -> Self {
+        Self {
+            items,
+            path: vec![0],
+            areas: Vec::new(),
+            cursor: usize::MAX,
+        }
+    }
+
+    /// Replaces the entries, keeping the selection and open sub-menus where
+    /// they still exist.
+    pub fn set_items(&mut self, items: Vec<MenuItem<T>>) {
+        self.items = items;
+        let mut items = &self.items;
+        for depth in 0..self.path.len() {
+            let index = self.path[depth].min(items.len().saturating_sub(1));
+            self.path[depth] = index;
+            match items.get(index).map(|item| &item.kind) {
+                Some(ItemKind::SubMenu(children)) if depth + 1 < self.path.len() => {
+                    items = children;
+                }
+                _ => {
+                    self.path.truncate(depth + 1);
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Back to the first entry of the top level.
+    pub fn reset(&mut self) {
+        self.path = vec![0];
+        self.cursor = usize::MAX;
+    }
+
+    /// Opens the sub-menu at `index` of the top level, e.g. to jump straight
+    /// to a confirmation.
+    pub fn open(&mut self, index: usize) {
+        self.path = vec![index];
+        if let Some(ItemKind::SubMenu(children)) = self.items.get(index).map(|item| &item.kind) {
+            if !children.is_empty() {
+                self.path.push(0);
+            }
+        }
+    }
+
+    fn current_items(&self) -> &[MenuItem<T>] {
+        let mut items = &self.items;
+        for index in &self.path[..self.path.len() - 1] {
+            if let Some(ItemKind::SubMenu(children)) = items.get(*index).map(|item| &item.kind) {
+                items = children;
+            }
+        }
+        items
+    }
+
+    fn current_items_mut(&mut self) -> &mut Vec<MenuItem<T>> {
+        let mut items = &mut self.items;
+        for index in &self.path[..self.path.len() - 1] {
+            items = match &mut items[*index].kind {
+                ItemKind::SubMenu(children) => children,
+                _ => unreachable!("menu paths only lead through sub-menus"),
+            };
+        }
+        items
+    }
+
+    /// Labels of the sub-menus leading to the open level.
+    pub fn open_sub_menus(&self) -> Vec<&str> {
+        let mut labels = Vec::new();
+        let mut items = &self.items;
+        for index in &self.path[..self.path.len() - 1] {
+            let Some(item) = items.get(*index) else {
+                break;
+            };
+            if let ItemKind::SubMenu(children) = &item.kind {
+                labels.push(item.label.as_str());
+                items = children;
+            }
+        }
+        labels
+    }
+
+    pub fn selected(&self) -> usize {
+        self.path[self.path.len() - 1]
+    }
+
+    pub fn select(&mut self, index: usize) {
+        let count = self.current_items().len();
+        if count > 0 {
+            let last = self.path.len() - 1;
+            if self.path[last] != index % count {
+                // text entries are entered at the end
+                self.cursor = usize::MAX;
+            }
+            self.path[last] = index % count;
+        }
+    }
+
+    fn move_selection(&mut self, forward: bool) {
+        let count = self.current_items().len();
+        if count == 0 {
+            return;
+        }
+        let selected = self.selected();
+        self.select(if forward {
+            selected + 1
+        } else {
+            selected + count - 1
+        });
+    }
+
+    // whether the selected entry takes typed characters
+    fn is_typing(&self) -> bool {
+        matches!(
+            self.current_items()
+                .get(self.selected())
+                .map(|item| &item.kind),
+            Some(ItemKind::Text { .. })
+        )
+    }
+
+    // ←/→ on the selected entry
+    fn adjust(&mut self, direction: i8) -> MenuEvent<T> {
+        let selected = self.selected();
+        let Some(item) = self.current_items_mut().get_mut(selected) else {
+            return MenuEvent::None;
+        };
+        match &mut item.kind {
+            ItemKind::Slider {
+                id,
+                value,
+                min,
+                max,
+                step,
+            } => {
+                *value = (*value + *step * direction as f32).clamp(*min, *max);
+                MenuEvent::Slider(id.clone(), *value)
+            }
+            ItemKind::Choice {
+                id,
+                options,
+                selected,
+            } if !options.is_empty() => {
+                *selected = if direction > 0 {
+                    (*selected + 1) % options.len()
+                } else {
+                    (*selected + options.len() - 1) % options.len()
+                };
+                MenuEvent::Choice(id.clone(), *selected)
+            }
+            _ => MenuEvent::None,
+        }
+    }
+
+    fn back(&mut self) -> MenuEvent<T> {
+        if self.path.len() > 1 {
+            self.path.pop();
+            MenuEvent::None
+        } else {
+            MenuEvent::Back
+        }
+    }
+
+    // Enter on the selected entry
+    fn activate(&mut self) -> MenuEvent<T> {
+        let selected = self.selected();
+        let Some(item) = self.current_items().get(selected) else {
+            return MenuEvent::None;
+        };
+        match &item.kind {
+            ItemKind::Action(id) => MenuEvent::Activate(id.clone()),
+            ItemKind::Choice { .. } => self.adjust(1),
+            // Enter finishes a text field by moving on to the next entry
+            ItemKind::Text { .. } => {
+                self.move_selection(true);
+                MenuEvent::None
+            }
+            ItemKind::SubMenu(children) if !children.is_empty() => {
+                self.path.push(0);
+                MenuEvent::None
+            }
+            ItemKind::Back => self.back(),
+            ItemKind::Slider { .. } | ItemKind::SubMenu(_) => MenuEvent::None,
+        }
+    }
+
+    // the selected text entry's value and limits
+    fn text_mut(&mut self) -> Option<(T, &mut String, usize, usize)> {
+        let selected = self.selected();
+        match self
+            .current_items_mut()
+            .get_mut(selected)
+            .map(|item| &mut item.kind)
+        {
+            Some(ItemKind::Text {
+                id,
+                value,
+                max_graphemes,
+                max_width,
+            }) => Some((id.clone(), value, *max_graphemes, *max_width)),
+            _ => None,
+        }
+    }
+
+    fn edit_text(&mut self, code: KeyCode) -> MenuEvent<T> {
+        let cursor = self.cursor;
+        let Some((id, value, max_graphemes, max_width)) = self.text_mut() else {
+            return MenuEvent::None;
+        };
+        let length = value.graphemes(true).count();
+        let mut cursor = cursor.min(length);
+        // byte offset of a grapheme position
+        let offset = |value: &str, position: usize| {
+            value
+                .grapheme_indices(true)
+                .nth(position)
+                .map_or(value.len(), |(offset, _)| offset)
+        };
+        let changed = match code {
+            KeyCode::Left => {
+                cursor = cursor.saturating_sub(1);
+                false
+            }
+            KeyCode::Right => {
+                cursor = (cursor + 1).min(length);
+                false
+            }
+            KeyCode::Home => {
+                cursor = 0;
+                false
+            }
+            KeyCode::End => {
+                cursor = length;
+                false
+            }
+            KeyCode::Backspace if cursor > 0 => {
+                value.replace_range(offset(value, cursor - 1)..offset(value, cursor), "");
+                cursor -= 1;
+                true
+            }
+            KeyCode::Delete if cursor < length => {
+                value.replace_range(offset(value, cursor)..offset(value, cursor + 1), "");
+                true
+            }
+            KeyCode::Char(c) if !c.is_control() => {
+                let mut typed = value.clone();
+                let at = offset(value, cursor);
+                typed.insert(at, c);
+                let fits =
+                    typed.graphemes(true).count() <= max_graphemes && typed.width() <= max_width;
+                if fits {
+                    // a combining mark joins the character before the cursor
+                    cursor = typed[..at + c.len_utf8()].graphemes(true).count();
+                    *value = typed;
+                }
+                fits
+            }
+            _ => false,
+        };
+        let event = if changed {
+            MenuEvent::Text(id, value.clone())
+        } else {
+            MenuEvent::None
+        };
+        self.cursor = cursor;
+        event
+    }
+
+    pub fn handle_key(&mut self, key_event: KeyEvent) -> MenuEvent<T> {
+        match key_event.code {
+            KeyCode::Up => self.move_selection(false),
+            KeyCode::Down | KeyCode::Tab => self.move_selection(true),
+            KeyCode::BackTab => self.move_selection(false),
+            code @ (KeyCode::Char(_)
+            | KeyCode::Backspace
+            | KeyCode::Delete
+            | KeyCode::Left
+            | KeyCode::Right
+            | KeyCode::Home
+            | KeyCode::End)
+                if self.is_typing() =>
+            {
+                return self.edit_text(code);
+            }
+            KeyCode::Left => return self.adjust(-1),
+            KeyCode::Right => return self.adjust(1),
+            KeyCode::Enter => return self.activate(),
+            KeyCode::Esc => return self.back(),
+            _ => {}
+        }
+        MenuEvent::None
+    }
+
+    /// Hovering selects an entry, clicking acts like Enter, the wheel acts
+    /// like ←/→ and a right click goes back.
+    pub fn handle_mouse(&mut self, mouse_event: MouseEvent) -> MenuEvent<T> {
+        if let MouseEventKind::Down(MouseButton::Right) = mouse_event.kind {
+            return self.back();
+        }
+        let position = Position::new(mouse_event.column, mouse_event.row);
+        let Some(index) = self.areas.iter().position(|area| area.contains(position)) else {
+            return MenuEvent::None;
+        };
+        self.select(index);
+        match mouse_event.kind {
+            MouseEventKind::Down(MouseButton::Left) => self.activate(),
+            MouseEventKind::ScrollUp => self.adjust(1),
+            MouseEventKind::ScrollDown => self.adjust(-1),
+            _ => MenuEvent::None,
+        }
+    }
+
+    /// Draws the open level centered in `area`, double-spaced when it fits.
+    pub fn draw(&mut self, frame: &mut Frame, area: Rect, style: &MenuStyle) {
+        let selected = self.selected();
+        let cursor = self.cursor;
+        let lines = self
+            .current_items()
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                let line_style = if i == selected {
+                    style.selected
+                } else {
+                    style.normal
+                };
+                let mut spans = match &item.kind {
+                    ItemKind::Text { value, .. } if i == selected => {
+                        text_with_cursor(&item.label, value, cursor)
+                    }
+                    _ => vec![Span::raw(item.text())],
+                };
+                if style.markers {
+                    let (open, close) = if i == selected {
+                        ("> ", " <")
+                    } else {
+                        ("  ", "  ")
+                    };
+                    spans.insert(0, Span::raw(open));
+                    spans.push(Span::raw(close));
+                }
+                (Line::from(spans), line_style)
+            })
+            .collect::<Vec<_>>();
+
+        let line_height = if lines.len() * 2 <= area.height as usize + 1 {
+            2
+        } else {
+            1
+        };
+        // no gap after the last entry
+        let total_height = (lines.len() * line_height).saturating_sub(line_height - 1) as u16;
+        let start_y = area.y + area.height.saturating_sub(total_height) / 2;
+        self.areas.clear();
+        for (i, (text, line_style)) in lines.into_iter().enumerate() {
+            let y = start_y + (i * line_height) as u16;
+            if y >= area.bottom() {
+                break;
+            }
+            let line_area = Rect::new(area.x, y, area.width, 1);
+            frame.render_widget(
+                Paragraph::new(text)
+                    .style(line_style)
+                    .alignment(Alignment::Center),
+                line_area,
+            );
+            self.areas.push(line_area);
+        }
+    }
+}
+
+// "Label: value" with the cell under the cursor reversed, or a trailing "_"
+// when the cursor is at the end
+fn text_with_cursor(label: &str, value: &str, cursor: usize) -> Vec<Span<'static>> {
+    let graphemes = value.graphemes(true).collect::<Vec<_>>();
+    let cursor = cursor.min(graphemes.len());
+    let mut spans = vec![Span::raw(format!(
+        "{}: {}",
+        label,
+        graphemes[..cursor].concat()
+    ))];
+    match graphemes.get(cursor) {
+        Some(under) => {
+            spans.push(Span::raw(under.to_string()).reversed());
+            spans.push(Span::raw(graphemes[cursor + 1..].concat()));
+        }
+        None => spans.push(Span::raw("_")),
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_keys(menu: &mut Menu<u8>, codes: &[KeyCode]) -> MenuEvent<u8> {
+        let mut event = MenuEvent::None;
+        for code in codes {
+            event = menu.handle_key(key(*code));
+        }
+        event
+    }
+
+    fn text_menu(value: &str, max_graphemes: usize, max_width: usize) -> Menu<u8> {
+        Menu::new(vec![MenuItem::new(
+            "Name",
+            ItemKind::Text {
+                id: 1,
+                value: value.to_string(),
+                max_graphemes,
+                max_width,
+            },
+        )])
+    }
+
+    fn nested_items(confirm_entries: usize) -> Vec<MenuItem<u8>> {
+        let mut confirm = vec![MenuItem::back()];
+        confirm
+            .extend((0..confirm_entries).map(|i| MenuItem::new("Yes", ItemKind::Action(i as u8))));
+        vec![
+            MenuItem::new("Play", ItemKind::Action(10)),
+            MenuItem::new("Quit", ItemKind::SubMenu(confirm)),
+        ]
+    }
+
+    #[test]
+    fn cursor_moves_and_edits_in_place() {
+        let mut menu = text_menu("Bob", 16, 24);
+        // the cursor starts at the end
+        let event = type_keys(
+            &mut menu,
+            &[KeyCode::Left, KeyCode::Left, KeyCode::Char('e')],
+        );
+        assert_eq!(event, MenuEvent::Text(1, "Beob".to_string()));
+        let event = type_keys(&mut menu, &[KeyCode::Home, KeyCode::Delete]);
+        assert_eq!(event, MenuEvent::Text(1, "eob".to_string()));
+        let event = type_keys(&mut menu, &[KeyCode::End, KeyCode::Backspace]);
+        assert_eq!(event, MenuEvent::Text(1, "eo".to_string()));
+    }
+
+    #[test]
+    fn backspace_and_delete_stop_at_the_edges() {
+        let mut menu = text_menu("ab", 16, 24);
+        assert_eq!(type_keys(&mut menu, &[KeyCode::Delete]), MenuEvent::None);
+        assert_eq!(
+            type_keys(&mut menu, &[KeyCode::Home, KeyCode::Backspace]),
+            MenuEvent::None
+        );
+        assert_eq!(
+            type_keys(&mut menu, &[KeyCode::Left, KeyCode::Delete]),
+            MenuEvent::Text(1, "b".to_string())
+        );
+    }
+
+    #[test]
+    fn combining_mark_joins_the_character_before_it() {
+        let mut menu = text_menu("Zoe", 3, 24);
+        // at the limit, but the mark doesn't add a character
+        let event = type_keys(&mut menu, &[KeyCode::Char('\u{308}')]);
+        assert_eq!(event, MenuEvent::Text(1, "Zoe\u{308}".to_string()));
+        // one grapheme back, removing the whole "ë"
+        let event = type_keys(
+            &mut menu,
+            &[KeyCode::Left, KeyCode::Right, KeyCode::Backspace],
+        );
+        assert_eq!(event, MenuEvent::Text(1, "Zo".to_string()));
+    }
+
+    #[test]
+    fn typing_stops_at_the_width_limit() {
+        let mut menu = text_menu("東京", 16, 5);
+        assert_eq!(
+            type_keys(&mut menu, &[KeyCode::Char('都')]),
+            MenuEvent::None
+        );
+        assert_eq!(
+            type_keys(&mut menu, &[KeyCode::Char('a')]),
+            MenuEvent::Text(1, "東京a".to_string())
+        );
+        assert_eq!(type_keys(&mut menu, &[KeyCode::Char('b')]), MenuEvent::None);
+    }
+
+    #[test]
+    fn set_items_keeps_the_path_where_it_still_exists() {
+        let mut menu = Menu::new(nested_items(2));
+        menu.open(1);
+        menu.select(2);
+        menu.set_items(nested_items(2));
+        assert_eq!(menu.open_sub_menus(), vec!["Quit"]);
+        assert_eq!(menu.selected(), 2);
+
+        // fewer entries: the selection clamps to the last one
+        menu.set_items(nested_items(1));
+        assert_eq!(menu.selected(), 1);
+
+        // the sub-menu is gone: back to the top level
+        menu.set_items(vec![MenuItem::new("Play", ItemKind::Action(10))]);
+        assert!(menu.open_sub_menus().is_empty());
+        assert_eq!(menu.selected(), 0);
+        assert_eq!(
+            menu.handle_key(key(KeyCode::Enter)),
+            MenuEvent::Activate(10)
+        );
+    }
+
+    #[test]
+    fn open_jumps_into_a_sub_menu() {
+        let mut menu = Menu::new(nested_items(1));
+        menu.open(1);
+        assert_eq!(menu.open_sub_menus(), vec!["Quit"]);
+        assert_eq!(menu.selected(), 0);
+        assert_eq!(menu.handle_key(key(KeyCode::Enter)), MenuEvent::None);
+        assert!(menu.open_sub_menus().is_empty());
+        assert_eq!(menu.handle_key(key(KeyCode::Esc)), MenuEvent::Back);
+    }
+
+    #[test]
+    fn mouse_hovers_clicks_and_scrolls() {
+        let mut menu = Menu::new(vec![
+            MenuItem::new("Play", ItemKind::Action(10)),
+            MenuItem::new(
+                "Speed",
+                ItemKind::Slider {
+                    id: 20,
+                    value: 1.0,
+                    min: 0.0,
+                    max: 2.0,
+                    step: 0.5,
+                },
+            ),
+        ]);
+        menu.areas = vec![Rect::new(0, 0, 10, 1), Rect::new(0, 2, 10, 1)];
+        let mouse = |kind, row| MouseEvent {
+            kind,
+            column: 3,
+            row,
+            modifiers: KeyModifiers::NONE,
+        };
+
+        assert_eq!(
+            menu.handle_mouse(mouse(MouseEventKind::Moved, 2)),
+            MenuEvent::None
+        );
+        assert_eq!(menu.selected(), 1);
+        assert_eq!(
+            menu.handle_mouse(mouse(MouseEventKind::ScrollUp, 2)),
+            MenuEvent::Slider(20, 1.5)
+        );
+        assert_eq!(
+            menu.handle_mouse(mouse(MouseEventKind::Down(MouseButton::Left), 0)),
+            MenuEvent::Activate(10)
+        );
+        // between entries nothing happens
+        assert_eq!(
+            menu.handle_mouse(mouse(MouseEventKind::Down(MouseButton::Left), 1)),
+            MenuEvent::None
+        );
+        assert_eq!(
+            menu.handle_mouse(mouse(MouseEventKind::Down(MouseButton::Right), 1)),
+            MenuEvent::Back
+        );
+    }
+}
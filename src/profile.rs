@@ -0,0 +1,218 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::APP_DIR_NAME,
+    keybindings::{Action, KeyBindings},
+    rating::{RatedMatch, STARTING_RATING},
+};
+
+const PROFILES_FILE_NAME: &str = "profiles.toml";
+const HISTORY_EXPORT_FILE_NAME: &str = "rating-history.csv";
+
+/// Paddle colors a profile can pick, drawn the same whatever the theme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PaddleColor {
+    #[default]
+    Theme,
+    Red,
+    Orange,
+    Yellow,
+    Green,
+    Cyan,
+    Blue,
+    Purple,
+    Pink,
+    White,
+}
+
+impl PaddleColor {
+    pub const ALL: [PaddleColor; 10] = [
+        PaddleColor::Theme,
+        PaddleColor::Red,
+        PaddleColor::Orange,
+        PaddleColor::Yellow,
+        PaddleColor::Green,
+        PaddleColor::Cyan,
+        PaddleColor::Blue,
+        PaddleColor::Purple,
+        PaddleColor::Pink,
+        PaddleColor::White,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PaddleColor::Theme => "Theme",
+            PaddleColor::Red => "Red",
+            PaddleColor::Orange => "Orange",
+            PaddleColor::Yellow => "Yellow",
+            PaddleColor::Green => "Green",
+            PaddleColor::Cyan => "Cyan",
+            PaddleColor::Blue => "Blue",
+            PaddleColor::Purple => "Purple",
+            PaddleColor::Pink => "Pink",
+            PaddleColor::White => "White",
+        }
+    }
+
+    /// `None` keeps the theme's paddle color.
+    pub fn color(&self) -> Option<Color> {
+        match self {
+            PaddleColor::Theme => None,
+            PaddleColor::Red => Some(Color::Rgb(230, 57, 70)),
+            PaddleColor::Orange => Some(Color::Rgb(244, 140, 6)),
+            PaddleColor::Yellow => Some(Color::Rgb(255, 209, 102)),
+            PaddleColor::Green => Some(Color::Rgb(6, 214, 160)),
+            PaddleColor::Cyan => Some(Color::Rgb(17, 200, 230)),
+            PaddleColor::Blue => Some(Color::Rgb(58, 134, 255)),
+            PaddleColor::Purple => Some(Color::Rgb(131, 56, 236)),
+            PaddleColor::Pink => Some(Color::Rgb(255, 0, 110)),
+            PaddleColor::White => Some(Color::Rgb(240, 240, 240)),
+        }
+    }
+}
+
+/// Lifetime totals over every match the profile played.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Stats {
+    pub matches: u32,
+    pub wins: u32,
+    pub points: u32,
+    pub longest_rally: u32,
+    pub power_moves_used: u32,
+}
+
+/// What one finished or abandoned match adds to a profile's stats.
+pub struct MatchRecord {
+    pub won: bool,
+    pub points: u32,
+    pub longest_rally: u32,
+    pub power_moves_used: u32,
+}
+
+impl Stats {
+    pub fn record(&mut self, record: &MatchRecord) {
+        self.matches += 1;
+        self.wins += record.won as u32;
+        self.points += record.points;
+        self.longest_rally = self.longest_rally.max(record.longest_rally);
+        self.power_moves_used += record.power_moves_used;
+    }
+}
+
+/// A local player: who they are, how their paddle looks and which keys they
+/// like, plus their stats and rating.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    pub name: String,
+    pub paddle_color: PaddleColor,
+    pub rating: f64,
+    /// Only the keys for the side the profile plays on are used, so a
+    /// profile on player 2 takes its `p2_*` keys.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_bindings: Option<KeyBindings>,
+    pub stats: Stats,
+    /// Every rated match, oldest first.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<RatedMatch>,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            paddle_color: PaddleColor::default(),
+            rating: STARTING_RATING,
+            key_bindings: None,
+            stats: Stats::default(),
+            history: Vec::new(),
+        }
+    }
+}
+
+/// The actions a profile's own keys cover when it plays as player `slot`.
+pub fn slot_actions(slot: usize) -> [Action; 3] {
+    if slot == 0 {
+        [Action::P1Up, Action::P1Down, Action::P1Power]
+    } else {
+        [Action::P2Up, Action::P2Down, Action::P2Power]
+    }
+}
+
+impl Profile {
+    /// Puts this profile's keys for player `slot` into `bindings`.
+    pub fn apply_keys(&self, slot: usize, bindings: &mut KeyBindings) {
+        let Some(own) = self.key_bindings.as_ref() else {
+            return;
+        };
+        for action in slot_actions(slot) {
+            *bindings.keys_mut(action) = own.keys(action).clone();
+        }
+    }
+}
+
+// `[[profile]]` tables, in the order they're listed in the picker
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ProfilesFile {
+    #[serde(default, rename = "profile")]
+    profiles: Vec<Profile>,
+}
+
+/// `$XDG_DATA_HOME/terminal-pong/profiles.toml` (or the platform equivalent).
+pub fn default_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(APP_DIR_NAME).join(PROFILES_FILE_NAME))
+}
+
+/// Where the rating history is exported to, next to the profiles.
+pub fn history_export_path(profiles_path: &Path) -> PathBuf {
+    profiles_path
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join(HISTORY_EXPORT_FILE_NAME)
+}
+
+/// Reads the profiles at `path`. A missing file means no profiles yet.
+pub fn load(path: &Path) -> Result<Vec<Profile>, String> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Could not read {}: {}", path.display(), e)),
+    };
+    toml::from_str::<ProfilesFile>(&contents)
+        .map(|file| file.profiles)
+        .map_err(|e| {
+            format!(
+                "Invalid profiles {}: {}",
+                path.display(),
+                e.message().trim()
+            )
+        })
+}
+
+pub fn save(path: &Path, profiles: &[Profile]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Could not create {}: {}", parent.display(), e))?;
+    }
+    let file = ProfilesFile {
+        profiles: profiles.to_vec(),
+    };
+    let contents = toml::to_string_pretty(&file)
+        .map_err(|e| format!("Could not serialize profiles: {}", e))?;
+    fs::write(path, contents).map_err(|e| format!("Could not write {}: {}", path.display(), e))
+}
+
+/// Finds a profile by name, ignoring case.
+pub fn find_profile(profiles: &[Profile], name: &str) -> Option<usize> {
+    profiles
+        .iter()
+        .position(|profile| profile.name.to_lowercase() == name.to_lowercase())
+}
@@ -0,0 +1,270 @@
+use crossterm::event::{KeyEvent, MouseEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+
+use crate::{
+    bindings_editor::{BindingsAction, BindingsEditor},
+    color::ColorSupport,
+    game::{PLAYER_NAME_MAX_GRAPHEMES, PLAYER_NAME_MAX_WIDTH},
+    game_theme::ThemeColors,
+    helpers::validate_name,
+    keybindings::KeyBindings,
+    menu::{ItemKind, Menu, MenuEvent, MenuItem, MenuStyle},
+    profile::{PaddleColor, Profile},
+};
+
+#[derive(Debug, Clone, Copy)]
+enum ProfileField {
+    Name,
+    PaddleColor,
+    KeyBindings,
+    DefaultKeys,
+    Save,
+    Delete,
+}
+
+pub enum ProfileAction {
+    None,
+    Save, // the edited `profile`
+    Delete,
+    Cancel,
+}
+
+/// Sub-screen of the profile picker for creating or changing a profile.
+/// Works on a copy that's only kept when saved.
+#[derive(Debug)]
+pub struct ProfileEditor {
+    pub profile: Profile,
+    pub index: Option<usize>, // None for a new profile
+    taken_names: Vec<String>, // the other profiles, lowercased
+    default_bindings: KeyBindings,
+    menu: Menu<ProfileField>,
+    bindings_editor: Option<BindingsEditor>,
+    message: Option<String>,
+}
+
+impl ProfileEditor {
+    pub fn new(
+        profile: Profile,
+        index: Option<usize>,
+        taken_names: Vec<String>,
+        default_bindings: &KeyBindings,
+    ) -> Self {
+        let mut editor = Self {
+            profile,
+            index,
+            taken_names: taken_names.iter().map(|name| name.to_lowercase()).collect(),
+            default_bindings: default_bindings.clone(),
+            menu: Menu::new(Vec::new()),
+            bindings_editor: None,
+            message: None,
+        };
+        editor.menu.set_items(editor.items());
+        editor
+    }
+
+    fn items(&self) -> Vec<MenuItem<ProfileField>> {
+        let mut items = vec![
+            MenuItem::new(
+                "Name",
+                ItemKind::Text {
+                    id: ProfileField::Name,
+                    value: self.profile.name.clone(),
+                    max_graphemes: PLAYER_NAME_MAX_GRAPHEMES,
+                    max_width: PLAYER_NAME_MAX_WIDTH,
+                },
+            ),
+            MenuItem::new(
+                "Paddle Color",
+                ItemKind::Choice {
+                    id: ProfileField::PaddleColor,
+                    options: PaddleColor::ALL
+                        .iter()
+                        .map(|color| color.name().to_string())
+                        .collect(),
+                    selected: PaddleColor::ALL
+                        .iter()
+                        .position(|color| *color == self.profile.paddle_color)
+                        .unwrap_or(0),
+                },
+            ),
+            MenuItem::new("Key Bindings…", ItemKind::Action(ProfileField::KeyBindings)),
+        ];
+        if self.profile.key_bindings.is_some() {
+            items.push(MenuItem::new(
+                "Use Default Keys",
+                ItemKind::Action(ProfileField::DefaultKeys),
+            ));
+        }
+        items.push(MenuItem::new("Save", ItemKind::Action(ProfileField::Save)));
+        if self.index.is_some() {
+            items.push(MenuItem::new(
+                "Delete",
+                ItemKind::SubMenu(vec![
+                    MenuItem::new("Keep Profile", ItemKind::Back),
+                    MenuItem::new(
+                        format!("Delete {}", self.profile.name),
+                        ItemKind::Action(ProfileField::Delete),
+                    ),
+                ]),
+            ));
+        }
+        items.push(MenuItem::new("Cancel", ItemKind::Back));
+        items
+    }
+
+    fn apply(&mut self, menu_event: MenuEvent<ProfileField>) -> ProfileAction {
+        match menu_event {
+            MenuEvent::Text(ProfileField::Name, name) => self.profile.name = name,
+            MenuEvent::Choice(ProfileField::PaddleColor, index) => {
+                self.profile.paddle_color = PaddleColor::ALL[index];
+            }
+            MenuEvent::Activate(ProfileField::KeyBindings) => {
+                let bindings = self
+                    .profile
+                    .key_bindings
+                    .as_ref()
+                    .unwrap_or(&self.default_bindings);
+                self.bindings_editor = Some(BindingsEditor::new(bindings));
+            }
+            MenuEvent::Activate(ProfileField::DefaultKeys) => {
+                self.profile.key_bindings = None;
+            }
+            MenuEvent::Activate(ProfileField::Save) => {
+                let name = self.profile.name.trim().to_string();
+                let error = if name.is_empty() {
+                    Some("A profile needs a name".to_string())
+                } else if self.taken_names.contains(&name.to_lowercase()) {
+                    Some(format!("There's already a profile called {}", name))
+                } else {
+                    validate_name(&name)
+                        .err()
+                        .map(|e| format!("The name {}", e))
+                };
+                if error.is_none() {
+                    self.profile.name = name;
+                    return ProfileAction::Save;
+                }
+                self.message = error;
+            }
+            MenuEvent::Activate(ProfileField::Delete) => return ProfileAction::Delete,
+            MenuEvent::Back => return ProfileAction::Cancel,
+            _ => {}
+        }
+        self.menu.set_items(self.items());
+        ProfileAction::None
+    }
+
+    pub fn handle_key(&mut self, key_event: KeyEvent) -> ProfileAction {
+        if let Some(editor) = self.bindings_editor.as_mut() {
+            if let BindingsAction::Close = editor.handle_key(key_event) {
+                // keys matching the defaults don't need to be stored
+                self.profile.key_bindings =
+                    Some(editor.bindings.clone()).filter(|keys| *keys != self.default_bindings);
+                self.bindings_editor = None;
+                self.menu.set_items(self.items());
+            }
+            return ProfileAction::None;
+        }
+        let menu_event = self.menu.handle_key(key_event);
+        self.apply(menu_event)
+    }
+
+    pub fn handle_mouse(&mut self, mouse_event: MouseEvent) -> ProfileAction {
+        if self.bindings_editor.is_some() {
+            return ProfileAction::None;
+        }
+        let menu_event = self.menu.handle_mouse(mouse_event);
+        self.apply(menu_event)
+    }
+
+    pub fn draw(
+        &mut self,
+        frame: &mut Frame,
+        area: Rect,
+        colors: &ThemeColors,
+        selection: Style,
+        color_support: ColorSupport,
+    ) {
+        if let Some(editor) = self.bindings_editor.as_ref() {
+            editor.draw(frame, area, colors, selection);
+            return;
+        }
+        let title = if self.index.is_some() {
+            "Edit Profile"
+        } else {
+            "New Profile"
+        };
+        let block = Block::default()
+            .title(Line::from(title).style(Style::default().fg(colors.title)))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick)
+            .style(Style::default().fg(colors.menu));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let [menu_area, preview_area, stats_area, message_area] = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(2),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(inner);
+        let style = MenuStyle {
+            normal: Style::default().fg(colors.text),
+            selected: selection.fg(colors.input),
+            markers: true,
+        };
+        self.menu.draw(frame, menu_area, &style);
+
+        let paddle = self
+            .profile
+            .paddle_color
+            .color()
+            .map_or(colors.player_bar, |color| color_support.map(color));
+        let keys = if self.profile.key_bindings.is_some() {
+            "own keys"
+        } else {
+            "default keys"
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled("███", Style::default().fg(paddle)),
+                Span::styled(
+                    format!("  paddle, {}", keys),
+                    Style::default().fg(colors.text),
+                ),
+            ]))
+            .alignment(Alignment::Center),
+            preview_area,
+        );
+
+        let stats = &self.profile.stats;
+        frame.render_widget(
+            Paragraph::new(format!(
+                "Matches {}  ·  Wins {}  ·  Points {}  ·  Longest rally {}  ·  Power moves {}",
+                stats.matches,
+                stats.wins,
+                stats.points,
+                stats.longest_rally,
+                stats.power_moves_used
+            ))
+            .style(Style::default().fg(colors.text))
+            .alignment(Alignment::Center),
+            stats_area,
+        );
+        if let Some(message) = self.message.as_ref() {
+            frame.render_widget(
+                Paragraph::new(message.as_str())
+                    .style(Style::default().fg(colors.ball).bold())
+                    .alignment(Alignment::Center),
+                message_area,
+            );
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use std::{
+    fs,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::profile::Profile;
+
+pub const STARTING_RATING: f64 = 1200.0;
+// how far one result can move a rating
+const K_FACTOR: f64 = 32.0;
+// the AI's strength at difficulty 0.0, and what each 1.0 of difficulty adds
+const AI_BASE_RATING: f64 = 800.0;
+const AI_RATING_PER_DIFFICULTY: f64 = 500.0;
+/// Results shown as a profile's recent form.
+pub const FORM_LENGTH: usize = 5;
+
+/// One rated match from a profile's point of view.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RatedMatch {
+    pub timestamp: u64, // seconds since the Unix epoch
+    pub opponent: String,
+    pub won: bool,
+    pub points: u32,
+    pub opponent_points: u32,
+    pub rating_before: f64,
+    pub rating_after: f64,
+}
+
+/// The rating the AI plays at for a difficulty from 0.0 to 2.0.
+pub fn ai_rating(difficulty: f32) -> f64 {
+    (AI_BASE_RATING + AI_RATING_PER_DIFFICULTY * difficulty as f64).round()
+}
+
+/// Elo: the chance of beating `opponent`, from 0.0 to 1.0.
+fn expected_score(rating: f64, opponent: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent - rating) / 400.0))
+}
+
+/// The rating after a win or loss against `opponent`, to one decimal.
+pub fn updated_rating(rating: f64, opponent: f64, won: bool) -> f64 {
+    let actual = if won { 1.0 } else { 0.0 };
+    let rating = rating + K_FACTOR * (actual - expected_score(rating, opponent));
+    (rating * 10.0).round() / 10.0
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// Wins and losses over every rated match.
+pub fn record(profile: &Profile) -> (usize, usize) {
+    let wins = profile.history.iter().filter(|rated| rated.won).count();
+    (wins, profile.history.len() - wins)
+}
+
+/// The latest results, oldest first, e.g. "WLWWL".
+pub fn form(profile: &Profile) -> String {
+    let skip = profile.history.len().saturating_sub(FORM_LENGTH);
+    profile.history[skip..]
+        .iter()
+        .map(|rated| if rated.won { 'W' } else { 'L' })
+        .collect()
+}
+
+/// Profile indexes from the highest rating down. Profiles that haven't
+/// played a rated match yet come last.
+pub fn leaderboard(profiles: &[Profile]) -> Vec<usize> {
+    let mut order = (0..profiles.len()).collect::<Vec<_>>();
+    order.sort_by(|a, b| {
+        let (a, b) = (&profiles[*a], &profiles[*b]);
+        a.history
+            .is_empty()
+            .cmp(&b.history.is_empty())
+            .then(b.rating.total_cmp(&a.rating))
+    });
+    order
+}
+
+// quotes a CSV field when it needs it
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// Writes every profile's rated matches to `path` as CSV, oldest first.
+/// Returns how many matches were written.
+pub fn export_history(path: &Path, profiles: &[Profile]) -> Result<usize, String> {
+    let mut rows = profiles
+        .iter()
+        .flat_map(|profile| profile.history.iter().map(move |rated| (profile, rated)))
+        .collect::<Vec<_>>();
+    rows.sort_by_key(|(_, rated)| rated.timestamp);
+
+    let mut csv = String::from(
+        "timestamp,player,opponent,result,points,opponent_points,rating_before,rating_after\n",
+    );
+    for (profile, rated) in &rows {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{:.1},{:.1}\n",
+            rated.timestamp,
+            csv_field(&profile.name),
+            csv_field(&rated.opponent),
+            if rated.won { "win" } else { "loss" },
+            rated.points,
+            rated.opponent_points,
+            rated.rating_before,
+            rated.rating_after
+        ));
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Could not create {}: {}", parent.display(), e))?;
+    }
+    fs::write(path, csv).map_err(|e| format!("Could not write {}: {}", path.display(), e))?;
+    Ok(rows.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rated(timestamp: u64, won: bool) -> RatedMatch {
+        RatedMatch {
+            timestamp,
+            opponent: "Computer".to_string(),
+            won,
+            points: if won { 5 } else { 2 },
+            opponent_points: if won { 2 } else { 5 },
+            rating_before: STARTING_RATING,
+            rating_after: STARTING_RATING,
+        }
+    }
+
+    #[test]
+    fn even_match_moves_half_the_k_factor() {
+        assert_eq!(updated_rating(1200.0, 1200.0, true), 1216.0);
+        assert_eq!(updated_rating(1200.0, 1200.0, false), 1184.0);
+    }
+
+    #[test]
+    fn upsets_move_ratings_further() {
+        // 32 * (1 - 1/11) for the underdog, 32 * (1 - 10/11) for the favorite
+        assert_eq!(updated_rating(1000.0, 1400.0, true), 1029.1);
+        assert_eq!(updated_rating(1400.0, 1000.0, true), 1402.9);
+        assert_eq!(updated_rating(1400.0, 1000.0, false), 1370.9);
+    }
+
+    #[test]
+    fn ai_rating_follows_difficulty() {
+        assert_eq!(ai_rating(0.0), 800.0);
+        assert_eq!(ai_rating(1.0), 1300.0);
+        assert_eq!(ai_rating(2.0), 1800.0);
+    }
+
+    #[test]
+    fn record_and_form() {
+        let profile = Profile {
+            history: [true, false, true, true, false, false]
+                .iter()
+                .enumerate()
+                .map(|(i, won)| rated(i as u64, *won))
+                .collect(),
+            ..Profile::default()
+        };
+        assert_eq!(record(&profile), (3, 3));
+        assert_eq!(form(&profile), "LWWLL");
+    }
+
+    #[test]
+    fn unrated_profiles_rank_last() {
+        let profile = |rating: f64, played: bool| Profile {
+            rating,
+            history: if played {
+                vec![rated(0, true)]
+            } else {
+                Vec::new()
+            },
+            ..Profile::default()
+        };
+        let profiles = [
+            profile(1300.0, false),
+            profile(1190.0, true),
+            profile(1250.0, true),
+        ];
+        assert_eq!(leaderboard(&profiles), vec![2, 1, 0]);
+    }
+
+    #[test]
+    fn csv_fields_are_quoted_when_needed() {
+        assert_eq!(csv_field("Ana"), "Ana");
+        assert_eq!(csv_field("Smith, J"), "\"Smith, J\"");
+        assert_eq!(csv_field("\"Ace\""), "\"\"\"Ace\"\"\"");
+    }
+}
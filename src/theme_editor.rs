@@ -0,0 +1,273 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{
+    color,
+    game_theme::{Theme, ThemeColors},
+};
+
+const RGB_STEP: i16 = 5;
+const HUE_STEP: f32 = 5.0;
+const PERCENT_STEP: f32 = 0.02;
+
+pub enum EditorAction {
+    None,
+    Close,
+    Save(Theme),
+}
+
+/// In-place editor for the colors of a theme, opened from the settings
+/// screen. Works on a copy, so nothing changes until the result is saved.
+#[derive(Debug)]
+pub struct ThemeEditor {
+    pub colors: ThemeColors,
+    pub slot: usize, // index into ThemeColors::SLOT_NAMES
+    channel: usize,  // 0..3, R/G/B or H/S/L
+    hsl_mode: bool,
+    // editing in HSL keeps its own values so hue survives gray/black/white
+    hsl: (f32, f32, f32),
+    name_input: Option<String>,
+    pub error: Option<String>,
+}
+
+impl ThemeEditor {
+    pub fn new(base: &Theme) -> Self {
+        let mut editor = Self {
+            colors: base.colors,
+            slot: 0,
+            channel: 0,
+            hsl_mode: false,
+            hsl: (0.0, 0.0, 0.0),
+            name_input: None,
+            error: None,
+        };
+        editor.sync_hsl();
+        editor
+    }
+
+    pub fn is_naming(&self) -> bool {
+        self.name_input.is_some()
+    }
+
+    fn rgb(&self) -> (u8, u8, u8) {
+        color::to_rgb(self.colors.slots()[self.slot]).unwrap_or((0, 0, 0))
+    }
+
+    fn sync_hsl(&mut self) {
+        self.hsl = color::rgb_to_hsl(self.rgb());
+    }
+
+    pub fn select_slot(&mut self, slot: usize) {
+        self.slot = slot % ThemeColors::SLOT_NAMES.len();
+        self.sync_hsl();
+    }
+
+    fn adjust(&mut self, direction: i16) {
+        let (r, g, b) = if self.hsl_mode {
+            let (h, s, l) = &mut self.hsl;
+            match self.channel {
+                0 => *h = (*h + HUE_STEP * direction as f32).rem_euclid(360.0),
+                1 => *s = (*s + PERCENT_STEP * direction as f32).clamp(0.0, 1.0),
+                _ => *l = (*l + PERCENT_STEP * direction as f32).clamp(0.0, 1.0),
+            }
+            color::hsl_to_rgb(self.hsl)
+        } else {
+            let mut rgb = self.rgb();
+            let channel = match self.channel {
+                0 => &mut rgb.0,
+                1 => &mut rgb.1,
+                _ => &mut rgb.2,
+            };
+            *channel = (*channel as i16 + RGB_STEP * direction).clamp(0, 255) as u8;
+            rgb
+        };
+        *self.colors.slot_mut(self.slot) = Color::Rgb(r, g, b);
+    }
+
+    pub fn handle_key(&mut self, key_event: KeyEvent) -> EditorAction {
+        // naming the theme before saving
+        if let Some(name) = self.name_input.as_mut() {
+            match key_event.code {
+                KeyCode::Enter => {
+                    let name = name.trim().to_string();
+                    if name.is_empty() {
+                        self.error = Some("Theme name can't be empty".to_string());
+                        return EditorAction::None;
+                    }
+                    return EditorAction::Save(Theme {
+                        name,
+                        colors: self.colors,
+                    });
+                }
+                KeyCode::Esc => self.name_input = None,
+                KeyCode::Backspace => {
+                    name.pop();
+                }
+                KeyCode::Char(c) if name.chars().count() < 24 && !c.is_control() => name.push(c),
+                _ => {}
+            }
+            return EditorAction::None;
+        }
+
+        match key_event.code {
+            KeyCode::Esc => return EditorAction::Close,
+            KeyCode::Tab => self.select_slot(self.slot + 1),
+            KeyCode::BackTab => {
+                self.select_slot(self.slot + ThemeColors::SLOT_NAMES.len() - 1);
+            }
+            KeyCode::Up => self.channel = (self.channel + 2) % 3,
+            KeyCode::Down => self.channel = (self.channel + 1) % 3,
+            KeyCode::Left => self.adjust(-1),
+            KeyCode::Right => self.adjust(1),
+            KeyCode::Char('m') => {
+                self.hsl_mode = !self.hsl_mode;
+                self.sync_hsl();
+            }
+            KeyCode::Enter => {
+                self.error = None;
+                self.name_input = Some(String::new());
+            }
+            _ => {}
+        }
+        EditorAction::None
+    }
+
+    /// `court_colors` are the edited colors as they should be previewed, i.e.
+    /// already mapped to the terminal's palette or a simulated vision type.
+    pub fn draw(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        ui_colors: &ThemeColors,
+        court_colors: &ThemeColors,
+    ) {
+        let block = Block::default()
+            .title(Line::from("Theme Editor").style(Style::default().fg(ui_colors.title)))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick)
+            .style(Style::default().fg(ui_colors.accent));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let [controls_area, court_area] =
+            Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .spacing(1)
+                .areas(inner);
+
+        let (labels, values, maxima) = if self.hsl_mode {
+            let (h, s, l) = self.hsl;
+            (
+                ["H", "S", "L"],
+                [h, s * 100.0, l * 100.0],
+                [360.0, 100.0, 100.0],
+            )
+        } else {
+            let (r, g, b) = self.rgb();
+            (
+                ["R", "G", "B"],
+                [r as f32, g as f32, b as f32],
+                [255.0, 255.0, 255.0],
+            )
+        };
+
+        let mut lines = vec![
+            Line::from(format!("Slot: < {} >", ThemeColors::SLOT_NAMES[self.slot])),
+            Line::from(if self.hsl_mode {
+                "Mode: HSL"
+            } else {
+                "Mode: RGB"
+            }),
+            Line::from(""),
+        ];
+        let bar_width = controls_area.width.saturating_sub(10).max(4) as usize;
+        for i in 0..3 {
+            let filled = ((values[i] / maxima[i]) * bar_width as f32).round() as usize;
+            let marker = if i == self.channel { ">" } else { " " };
+            let style = if i == self.channel {
+                Style::default().fg(ui_colors.text).bold()
+            } else {
+                Style::default().fg(ui_colors.text)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("{}{} ", marker, labels[i]), style),
+                Span::styled(
+                    "█".repeat(filled.min(bar_width)),
+                    Style::default().fg(ui_colors.accent),
+                ),
+                Span::styled(
+                    "░".repeat(bar_width.saturating_sub(filled)),
+                    Style::default().fg(ui_colors.border),
+                ),
+                Span::styled(format!(" {:>3.0}", values[i]), style),
+            ]));
+        }
+        lines.push(Line::from(""));
+        match self.name_input.as_ref() {
+            Some(name) => lines.push(Line::from(format!("Name: {}_", name))),
+            None => lines.push(Line::from(
+                "Tab slot  ↑↓ channel  ←→ adjust  M RGB/HSL  Enter save  Esc cancel",
+            )),
+        }
+        if let Some(error) = self.error.as_ref() {
+            lines.push(Line::from(error.as_str()).style(Style::default().fg(ui_colors.ball)));
+        }
+        frame.render_widget(
+            Paragraph::new(lines)
+                .style(Style::default().fg(ui_colors.text))
+                .wrap(Wrap { trim: false }),
+            controls_area,
+        );
+
+        self.draw_mini_court(frame, court_area, court_colors);
+    }
+
+    // a tiny static court drawn with the colors being edited
+    fn draw_mini_court(&self, frame: &mut Frame, area: Rect, colors: &ThemeColors) {
+        let [court] = Layout::vertical([Constraint::Max(9)])
+            .flex(Flex::Center)
+            .areas(area);
+        let block = Block::default()
+            .title(Line::from(" P1(2) ── pong ── P2(1) ").style(Style::default().fg(colors.text)))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick)
+            .style(Style::default().fg(colors.border).bg(colors.background));
+        let inner = block.inner(court);
+        frame.render_widget(block, court);
+        if inner.width < 8 || inner.height < 4 {
+            return;
+        }
+
+        let paddle_height = 3.min(inner.height);
+        let left = Rect::new(inner.x, inner.y + 1, 1, paddle_height);
+        let right = Rect::new(inner.right() - 1, inner.y, 1, paddle_height);
+        frame.render_widget(
+            Block::default().style(Style::default().bg(colors.player_bar)),
+            left,
+        );
+        frame.render_widget(
+            Block::default().style(Style::default().bg(colors.player_bar_power)),
+            right,
+        );
+
+        let ball = Rect::new(inner.x + inner.width / 2, inner.y + inner.height / 2, 2, 1);
+        frame.render_widget(
+            Paragraph::new("██").style(Style::default().fg(colors.ball)),
+            ball,
+        );
+
+        let footer = Rect::new(inner.x, inner.bottom() - 1, inner.width, 1);
+        frame.render_widget(
+            Paragraph::new("paused")
+                .style(Style::default().fg(colors.accent))
+                .alignment(Alignment::Center),
+            footer,
+        );
+    }
+}
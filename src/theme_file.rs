@@ -0,0 +1,308 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    color,
+    game_theme::{find_theme, Theme, ThemeColors},
+};
+
+/// On-disk theme format: every [`ThemeColors`] slot as a `#rrggbb` string.
+/// `background` may also be `"reset"` to keep the terminal's own background.
+/// The menu slots are optional so themes written before they existed still
+/// load; they fall back to the closest court color.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeFile {
+    name: Option<String>,
+    background: String,
+    border: String,
+    text: String,
+    accent: String,
+    player_bar: String,
+    player_bar_power: String,
+    ball: String,
+    menu: Option<String>,
+    selection: Option<String>,
+    title: Option<String>,
+    input: Option<String>,
+}
+
+/// Loads every `*.toml` theme in `dir`, skipping files that fail to parse or
+/// clash with an existing theme name. Returns the valid themes along with one
+/// message per rejected file. A missing directory yields no themes and no
+/// errors.
+pub fn load_custom_themes(dir: &Path, existing: &[Theme]) -> (Vec<Theme>, Vec<String>) {
+    let mut themes: Vec<Theme> = Vec::new();
+    let mut errors = Vec::new();
+
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return (themes, errors),
+        Err(e) => {
+            errors.push(format!("Could not read {}: {}", dir.display(), e));
+            return (themes, errors);
+        }
+    };
+
+    let mut paths = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    for path in paths {
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        match load_theme(&path) {
+            Ok(theme) => {
+                if find_theme(existing, &theme.name).is_some()
+                    || find_theme(&themes, &theme.name).is_some()
+                {
+                    errors.push(format!(
+                        "{}: a theme named \"{}\" already exists",
+                        file_name, theme.name
+                    ));
+                } else {
+                    themes.push(theme);
+                }
+            }
+            Err(e) => errors.push(format!("{}: {}", file_name, e)),
+        }
+    }
+
+    (themes, errors)
+}
+
+/// Writes `theme` to a new file in `dir`, named after the theme. Refuses to
+/// overwrite an existing file.
+pub fn save_theme(dir: &Path, theme: &Theme) -> Result<PathBuf, String> {
+    let slug = theme
+        .name
+        .chars()
+        .flat_map(|c| {
+            // any script's letters and digits, so "東京" gets a file name too
+            if c.is_alphanumeric() {
+                c.to_lowercase().collect::<Vec<_>>()
+            } else {
+                vec!['-']
+            }
+        })
+        .collect::<String>();
+    let slug = slug.trim_matches('-');
+    if slug.is_empty() {
+        return Err("Theme name needs at least one letter or digit".to_string());
+    }
+
+    let path = dir.join(format!("{}.toml", slug));
+    if path.exists() {
+        return Err(format!("{} already exists", path.display()));
+    }
+
+    let colors = &theme.colors;
+    let file = ThemeFile {
+        name: Some(theme.name.clone()),
+        background: to_hex_color(colors.background),
+        border: to_hex_color(colors.border),
+        text: to_hex_color(colors.text),
+        accent: to_hex_color(colors.accent),
+        player_bar: to_hex_color(colors.player_bar),
+        player_bar_power: to_hex_color(colors.player_bar_power),
+        ball: to_hex_color(colors.ball),
+        menu: Some(to_hex_color(colors.menu)),
+        selection: Some(to_hex_color(colors.selection)),
+        title: Some(to_hex_color(colors.title)),
+        input: Some(to_hex_color(colors.input)),
+    };
+    let contents =
+        toml::to_string_pretty(&file).map_err(|e| format!("Could not serialize theme: {}", e))?;
+
+    fs::create_dir_all(dir).map_err(|e| format!("Could not create {}: {}", dir.display(), e))?;
+    fs::write(&path, contents).map_err(|e| format!("Could not write {}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+fn load_theme(path: &Path) -> Result<Theme, String> {
+    let contents = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let file: ThemeFile = toml::from_str(&contents).map_err(|e| e.message().trim().to_string())?;
+
+    let name = match file.name {
+        Some(name) if !name.trim().is_empty() => name.trim().to_string(),
+        _ => path
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned(),
+    };
+
+    let text = parse_hex_color("text", &file.text)?;
+    let accent = parse_hex_color("accent", &file.accent)?;
+    let border = parse_hex_color("border", &file.border)?;
+    let optional = |field: &str, value: Option<&String>, fallback: Color| match value {
+        Some(value) => parse_hex_color(field, value),
+        None => Ok(fallback),
+    };
+
+    let colors = ThemeColors {
+        background: if file.background.eq_ignore_ascii_case("reset") {
+            Color::Reset
+        } else {
+            parse_hex_color("background", &file.background)?
+        },
+        border,
+        text,
+        accent,
+        player_bar: parse_hex_color("player_bar", &file.player_bar)?,
+        player_bar_power: parse_hex_color("player_bar_power", &file.player_bar_power)?,
+        ball: parse_hex_color("ball", &file.ball)?,
+        menu: optional("menu", file.menu.as_ref(), accent)?,
+        selection: optional("selection", file.selection.as_ref(), text)?,
+        title: optional("title", file.title.as_ref(), border)?,
+        input: optional("input", file.input.as_ref(), accent)?,
+    };
+
+    Ok(Theme { name, colors })
+}
+
+fn to_hex_color(color: Color) -> String {
+    match color::to_rgb(color) {
+        Some((r, g, b)) => format!("#{:02x}{:02x}{:02x}", r, g, b),
+        None => "reset".to_string(),
+    }
+}
+
+fn parse_hex_color(field: &str, value: &str) -> Result<Color, String> {
+    let hex = value.trim().trim_start_matches('#');
+    let invalid = || {
+        format!(
+            "`{}` must be a hex color like \"#1e90ff\", got \"{}\"",
+            field, value
+        )
+    };
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| invalid());
+    Ok(Color::Rgb(channel(0)?, channel(2)?, channel(4)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUNSET: &str = r##"
+name = "Sunset"
+background = "reset"
+border = "#ff8800"
+text = "#FFFFFF"
+accent = "#aa3300"
+player_bar = "#ffcc00"
+player_bar_power = "#ff0000"
+ball = "#ffffff"
+"##;
+
+    // an empty directory of its own for each test
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "terminal-pong-themes-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn parses_hex_colors() {
+        assert_eq!(
+            parse_hex_color("ball", "#1e90ff"),
+            Ok(Color::Rgb(30, 144, 255))
+        );
+        assert_eq!(
+            parse_hex_color("ball", " 00FF00 "),
+            Ok(Color::Rgb(0, 255, 0))
+        );
+        assert!(parse_hex_color("ball", "#fff").is_err());
+        assert!(parse_hex_color("ball", "#gg0000").is_err());
+    }
+
+    #[test]
+    fn loads_themes_and_reports_bad_files() {
+        let dir = temp_dir("load");
+        fs::write(dir.join("sunset.toml"), SUNSET).unwrap();
+        fs::write(dir.join("broken.toml"), SUNSET.replace("#ffcc00", "yellow")).unwrap();
+        fs::write(
+            dir.join("extra.toml"),
+            format!("{}\nsparkle = true", SUNSET),
+        )
+        .unwrap();
+        fs::write(dir.join("notes.txt"), "not a theme").unwrap();
+        fs::write(
+            dir.join("nord.toml"),
+            SUNSET.replace("\"Sunset\"", "\"Nord\""),
+        )
+        .unwrap();
+
+        let (themes, errors) = load_custom_themes(&dir, &Theme::builtin());
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(themes.len(), 1);
+        let colors = &themes[0].colors;
+        assert_eq!(themes[0].name, "Sunset");
+        assert_eq!(colors.background, Color::Reset);
+        assert_eq!(colors.text, Color::Rgb(255, 255, 255));
+        // missing menu colors fall back to court colors
+        assert_eq!(colors.menu, colors.accent);
+        assert_eq!(colors.title, colors.border);
+
+        assert_eq!(errors.len(), 3);
+        assert!(errors[0].starts_with("broken.toml: `player_bar`"));
+        assert!(errors[1].starts_with("extra.toml:"));
+        assert!(errors[2].contains("already exists"));
+    }
+
+    #[test]
+    fn saved_themes_load_back() {
+        let dir = temp_dir("save");
+        let theme = Theme {
+            name: "My Theme!".to_string(),
+            ..Theme::builtin()[0].clone()
+        };
+        let path = save_theme(&dir, &theme).unwrap();
+        assert_eq!(path, dir.join("my-theme.toml"));
+        assert!(save_theme(&dir, &theme).is_err());
+
+        let (themes, errors) = load_custom_themes(&dir, &[]);
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(errors.is_empty());
+        assert_eq!(themes[0].name, "My Theme!");
+        assert_eq!(themes[0].colors, theme.colors);
+    }
+
+    #[test]
+    fn file_names_keep_unicode_letters() {
+        let dir = temp_dir("unicode");
+        let theme = |name: &str| Theme {
+            name: name.to_string(),
+            ..Theme::builtin()[0].clone()
+        };
+        let tokyo = save_theme(&dir, &theme("東京 Night")).unwrap();
+        let cafe = save_theme(&dir, &theme("CAFÉ")).unwrap();
+        let empty = save_theme(&dir, &theme("!!!"));
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(tokyo, dir.join("東京-night.toml"));
+        assert_eq!(cafe, dir.join("café.toml"));
+        assert!(empty.is_err());
+    }
+
+    #[test]
+    fn missing_directory_is_not_an_error() {
+        let dir = std::env::temp_dir().join("terminal-pong-themes-missing-dir");
+        let (themes, errors) = load_custom_themes(&dir, &[]);
+        assert!(themes.is_empty() && errors.is_empty());
+    }
+}